@@ -0,0 +1,149 @@
+//! Device address
+
+use super::{Error, Max6955, Register, DEFAULT_SLAVE_ADDR};
+use core::convert::TryFrom;
+use hal::blocking::i2c::{Write, WriteRead};
+
+/// Device address, `0x60` ~ `0x6F`. See table 5 in the datasheet.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Address(u8);
+
+impl Address {
+    /// Default address `0x60`
+    pub const DEFAULT: Address = Address(DEFAULT_SLAVE_ADDR);
+
+    /// Lowest device address
+    pub const MIN: u8 = 0x60;
+
+    /// Highest device address
+    pub const MAX: u8 = 0x6F;
+
+    /// Check a 7-bit address
+    /// # Arguments
+    /// * `addr` - `0x60` ~ `0x6F`
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidAddress` - `addr` is outside the range
+    pub fn new(addr: u8) -> Result<Self, Error> {
+        if (Address::MIN..=Address::MAX).contains(&addr) {
+            Ok(Address(addr))
+        } else {
+            Err(Error::InvalidAddress)
+        }
+    }
+
+    /// Address selected by the strapping of pins AD1 and AD0, see table 5 in the datasheet
+    /// # Arguments
+    /// * `ad1` - connection of pin AD1
+    /// * `ad0` - connection of pin AD0
+    pub const fn from_pins(ad1: PinStrap, ad0: PinStrap) -> Self {
+        Address(Address::MIN + 4 * ad1 as u8 + ad0 as u8)
+    }
+
+    /// return the 7-bit address
+    pub fn value(self) -> u8 {
+        self.0
+    }
+}
+
+/// Connection of an address pin
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PinStrap {
+    /// Connected to GND
+    Gnd = 0,
+    /// Connected to V+
+    Vplus = 1,
+    /// Connected to SDA
+    Sda = 2,
+    /// Connected to SCL
+    Scl = 3,
+}
+
+impl TryFrom<u8> for Address {
+    type Error = Error;
+
+    fn try_from(addr: u8) -> Result<Self, Error> {
+        Address::new(addr)
+    }
+}
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: WriteRead<Error = E> + Write<Error = E>,
+{
+    /// Scan the Bus for MAX6955s
+    ///
+    /// Returns an iterator over the addresses `0x60` ~ `0x6F` at which a device acknowledges a
+    /// read of the configuration register. Addresses are tried as the iterator advances.
+    /// # Arguments
+    ///
+    /// * `i2c` - I2C interface
+    pub fn scan(i2c: &mut I2C) -> Scan<'_, I2C> {
+        Scan {
+            i2c,
+            next: Address::MIN,
+        }
+    }
+}
+
+/// Iterator returned by `Max6955::scan`
+pub struct Scan<'a, I2C> {
+    i2c: &'a mut I2C,
+    next: u8,
+}
+
+impl<'a, I2C, E> Iterator for Scan<'a, I2C>
+where
+    I2C: WriteRead<Error = E> + Write<Error = E>,
+{
+    type Item = Address;
+
+    fn next(&mut self) -> Option<Address> {
+        while self.next <= Address::MAX {
+            let addr = self.next;
+            self.next += 1;
+            let mut config: [u8; 1] = [0];
+            if self
+                .i2c
+                .write_read(addr, &[Register::Configuration.addr()], &mut config)
+                .is_ok()
+            {
+                return Some(Address(addr));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Address, PinStrap};
+    use core::convert::TryFrom;
+    use Error;
+
+    #[test]
+    fn pin_strapping_selects_the_address() {
+        assert_eq!(
+            Address::from_pins(PinStrap::Gnd, PinStrap::Gnd),
+            Address::DEFAULT
+        );
+        assert_eq!(
+            Address::from_pins(PinStrap::Gnd, PinStrap::Scl).value(),
+            0x63
+        );
+        assert_eq!(
+            Address::from_pins(PinStrap::Vplus, PinStrap::Gnd).value(),
+            0x64
+        );
+        assert_eq!(
+            Address::from_pins(PinStrap::Sda, PinStrap::Vplus).value(),
+            0x69
+        );
+        assert_eq!(
+            Address::from_pins(PinStrap::Scl, PinStrap::Scl).value(),
+            Address::MAX
+        );
+        assert_eq!(Address::try_from(0x70), Err(Error::InvalidAddress));
+    }
+}
@@ -0,0 +1,213 @@
+//! Blinking of individual digits through the two digit planes
+
+use super::{
+    ascii, BlinkConfig, BlinkMode, BlinkPhase, BlinkRate, BlinkSync, ConfigBitFlag, Interface,
+    Max6955, Plane, Register,
+};
+use bit_field::BitField;
+use hal::blocking::delay::DelayMs;
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Blink a Single Digit
+    ///
+    /// Blinking digits are blank in plane P1, the other digits show the same data in both planes.
+    /// Global blinking is enabled while any digit blinks, and text writes keep plane P1 in step.
+    /// # Arguments
+    ///
+    /// * `digit` - text position `0` ~ `7`
+    /// * `enable` - `true`: blink, `false`: steady
+    pub fn set_digit_blink(&mut self, digit: u8, enable: bool) -> Result<(), E> {
+        self.blink_mask.set_bit((digit & 0x07) as usize, enable);
+        self.write_blink_plane()?;
+        self.set_configuration_bit(ConfigBitFlag::Blink, self.blink_mask != 0)
+    }
+
+    /// Blink Selected Digits
+    ///
+    /// Writes plane P1 in one transaction with the masked digits blank, then sets blink enable
+    /// and rate. Blinking is disabled when `mask` is `0`.
+    /// # Arguments
+    ///
+    /// * `mask` - bit `n` set: text position `n` blinks
+    /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
+    pub fn set_blink_mask(&mut self, mask: u8, rate: BlinkRate) -> Result<(), E> {
+        self.blink_mask = mask;
+        self.write_blink_plane()?;
+        let mode = if mask != 0 {
+            BlinkMode::Enable
+        } else {
+            BlinkMode::Disable
+        };
+        self.set_blink(mode, rate)
+    }
+
+    /// Blinking Edit Cursor
+    ///
+    /// Blinks the digit at `position` alone, as a cursor while that digit is adjusted.
+    /// The digit keeps blinking through `write_char` and other text writes.
+    /// # Arguments
+    ///
+    /// * `position` - text position `0` ~ `7` under edit, `None` to stop blinking
+    /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
+    pub fn set_edit_cursor(&mut self, position: Option<u8>, rate: BlinkRate) -> Result<(), E> {
+        let mask = position.map_or(0, |position| 1 << (position & 0x07));
+        self.set_blink_mask(mask, rate)
+    }
+
+    /// Set the Blink Timing Synchronization bit
+    ///
+    /// The bit acts once. It is not kept in the driver's copy of the configuration, so later
+    /// configuration writes leave the blink timing alone.
+    /// # Arguments
+    ///
+    /// * `sync` - `BlinkSync::Reset`: reset the blink timing counters,
+    ///   `BlinkSync::Unaffected`: leave them running
+    pub fn set_blink_timing(&mut self, sync: BlinkSync) -> Result<(), E> {
+        let mut config = self.config()?;
+        config.set_bit(ConfigBitFlag::BlinkTiming.value(), sync.value());
+        self.write_register(Register::Configuration, config)
+    }
+
+    /// Synchronize Blink Timing
+    ///
+    /// Resets the blink timing counters. Doing this on several MAX6955s back to back starts
+    /// their blink clocks together, so a large panel blinks in lockstep.
+    pub fn sync_blink_timing(&mut self) -> Result<(), E> {
+        self.set_blink_timing(BlinkSync::Reset)
+    }
+
+    /// Read Blink Phase
+    ///
+    /// Returns which plane the blink clock currently shows.
+    pub fn blink_phase(&mut self) -> Result<BlinkPhase, E> {
+        let config: u8 = self.read_register(Register::Configuration)?;
+        Ok(BlinkPhase::from(
+            config.get_bit(ConfigBitFlag::BlinkPhase.value()),
+        ))
+    }
+
+    /// Read Blink Configuration
+    ///
+    /// Returns blink enable, rate and phase decoded from the configuration register.
+    pub fn blink_config(&mut self) -> Result<BlinkConfig, E> {
+        let config: u8 = self.read_register(Register::Configuration)?;
+        Ok(BlinkConfig {
+            mode: BlinkMode::from(config.get_bit(ConfigBitFlag::Blink.value())),
+            rate: BlinkRate::from(config.get_bit(ConfigBitFlag::BlinkRate.value())),
+            phase: BlinkPhase::from(config.get_bit(ConfigBitFlag::BlinkPhase.value())),
+        })
+    }
+
+    /// Wait for the Blink Phase to Change
+    ///
+    /// Polls the blink phase until it flips and returns the new phase, so that an update can
+    /// be timed to the phase in which it is not visible. Returns `None` right away when the
+    /// display is shut down or not blinking, and after `timeout_ms` without a flip.
+    /// # Arguments
+    ///
+    /// * `poll_ms` - polling interval in milliseconds
+    /// * `timeout_ms` - longest wait in milliseconds, e.g. `1000` covers a slow blink cycle
+    /// * `delay` - delay provider
+    pub fn wait_blink_edge<D: DelayMs<u32>>(
+        &mut self,
+        poll_ms: u32,
+        timeout_ms: u32,
+        delay: &mut D,
+    ) -> Result<Option<BlinkPhase>, E> {
+        let config = self.configuration()?;
+        if config.shutdown || !config.blink.value() {
+            return Ok(None);
+        }
+        let mut waited: u32 = 0;
+        while waited < timeout_ms {
+            delay.delay_ms(poll_ms);
+            waited = waited.saturating_add(poll_ms.max(1));
+            let phase = self.blink_phase()?;
+            if phase != config.phase {
+                return Ok(Some(phase));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Alternate Two Messages
+    ///
+    /// Writes `a` to plane P0 and `b` to plane P1 and enables blinking, so the chip alternates
+    /// between them by itself. Per-digit blinking is turned off.
+    /// # Arguments
+    ///
+    /// * `a` - text shown in the on phase
+    /// * `b` - text shown in the off phase
+    /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
+    pub fn set_blink_messages(&mut self, a: &str, b: &str, rate: BlinkRate) -> Result<(), E> {
+        self.blink_mask = 0;
+        self.write_str(a)?;
+        let mut digits: [u8; 8] = [b' '; 8];
+        for (digit, c) in digits.iter_mut().take(self.digits).zip(b.chars()) {
+            *digit = ascii(c);
+        }
+        self.write_plane(Plane::P1, &digits)?;
+        self.set_blink(BlinkMode::Enable, rate)
+    }
+
+    /// Swap Planes
+    ///
+    /// Exchanges the contents of plane P0 and plane P1 by reading both back.
+    /// Per-digit blinking is turned off.
+    pub fn swap_planes(&mut self) -> Result<(), E> {
+        self.blink_mask = 0;
+        let p0 = self.read_display(Plane::P0)?;
+        let p1 = self.read_display(Plane::P1)?;
+        self.write_digits(&p1)?;
+        self.write_plane(Plane::P1, &p0)
+    }
+
+    /// Write plane P1 from the plane P0 shadow with the blinking digits blanked
+    pub(crate) fn write_blink_plane(&mut self) -> Result<(), E> {
+        let mut digits = self.shadow();
+        self.blank_blinking(&mut digits);
+        self.write_plane(Plane::P1, &digits)
+    }
+
+    /// Blank the digits that blink
+    pub(crate) fn blank_blinking(&self, digits: &mut [u8; 8]) {
+        for (i, digit) in digits.iter_mut().enumerate() {
+            if self.blink_mask.get_bit(i) {
+                *digit = b' ';
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mock::{Mock, NoDelay};
+    use {BlinkMode, BlinkRate, Max6955, Register};
+
+    #[test]
+    fn blink_timing_reset_is_written_once() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        max6955.sync_blink_timing().unwrap();
+        max6955.powerup().unwrap();
+        assert_eq!(max6955.i2c.config_writes(), &[0x10, 0x01]);
+    }
+
+    #[test]
+    fn wait_blink_edge_gives_up_without_blinking() {
+        let mut max6955 = Max6955::new(Mock::with(Register::Configuration, 0x01)).unwrap();
+        assert!(matches!(
+            max6955.wait_blink_edge(10, 1000, &mut NoDelay),
+            Ok(None)
+        ));
+        max6955
+            .set_blink(BlinkMode::Enable, BlinkRate::Slow)
+            .unwrap();
+        assert!(matches!(
+            max6955.wait_blink_edge(10, 1000, &mut NoDelay),
+            Ok(None)
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+//! Breathing brightness animation
+
+use super::{Interface, Max6955, MAX_INTENSITY};
+use hal::blocking::delay::DelayMs;
+
+/// Ramps the global intensity up and down between two levels
+///
+/// Call `step` every `step_ms` milliseconds, e.g. from a timer interrupt, or let `run` drive it with a delay.
+pub struct Breathe {
+    low: u8,
+    high: u8,
+    level: u8,
+    rising: bool,
+    step_ms: u32,
+}
+
+impl Breathe {
+    /// Create an animation
+    /// # Arguments
+    /// * `low` - dimmest intensity level `0` ~ `15`
+    /// * `high` - brightest intensity level `0` ~ `15`
+    /// * `period_ms` - time for one full cycle, dim to bright and back, in milliseconds
+    pub fn new(low: u8, high: u8, period_ms: u32) -> Self {
+        let high = high.min(MAX_INTENSITY);
+        let low = low.min(high);
+        let steps = 2 * (high - low) as u32;
+        Breathe {
+            low,
+            high,
+            level: low,
+            rising: true,
+            step_ms: period_ms / steps.max(1),
+        }
+    }
+
+    /// Interval between `step` calls in milliseconds for the configured period
+    pub fn step_ms(&self) -> u32 {
+        self.step_ms
+    }
+
+    /// Move one intensity level on and write it
+    /// # Arguments
+    /// * `max6955` - display to animate
+    pub fn step<I2C, E>(&mut self, max6955: &mut Max6955<I2C>) -> Result<(), E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        if self.rising && self.level >= self.high {
+            self.rising = false;
+        } else if !self.rising && self.level <= self.low {
+            self.rising = true;
+        }
+        if self.low < self.high {
+            if self.rising {
+                self.level += 1;
+            } else {
+                self.level -= 1;
+            }
+        }
+        max6955.set_global_intensity(self.level)
+    }
+
+    /// Run whole cycles, blocking
+    /// # Arguments
+    /// * `max6955` - display to animate
+    /// * `cycles` - number of cycles
+    /// * `delay` - delay provider
+    pub fn run<I2C, E, D>(
+        &mut self,
+        max6955: &mut Max6955<I2C>,
+        cycles: u32,
+        delay: &mut D,
+    ) -> Result<(), E>
+    where
+        I2C: Interface<Error = E>,
+        D: DelayMs<u32>,
+    {
+        let steps = 2 * (self.high - self.low) as u32;
+        for _ in 0..cycles {
+            for _ in 0..steps {
+                self.step(max6955)?;
+                delay.delay_ms(self.step_ms);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Breathe;
+    use hal::blocking::delay::DelayMs;
+    use mock::Mock;
+    use {Max6955, Register};
+
+    /// Delay that counts its calls and stops the test after `limit` of them
+    struct Steps {
+        count: u32,
+        limit: u32,
+    }
+
+    impl DelayMs<u32> for Steps {
+        fn delay_ms(&mut self, _ms: u32) {
+            self.count += 1;
+            assert!(self.count < self.limit, "stopped");
+        }
+    }
+
+    #[test]
+    fn run_returns_to_the_low_level() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        let mut breathe = Breathe::new(2, 5, 600);
+        let mut delay = Steps {
+            count: 0,
+            limit: u32::MAX,
+        };
+        breathe.run(&mut max6955, 2, &mut delay).unwrap();
+        assert_eq!(delay.count, 12);
+        assert_eq!(max6955.i2c.get(Register::GlobalIntensity), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "stopped")]
+    fn run_takes_any_number_of_cycles() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        let mut breathe = Breathe::new(0, 15, 3000);
+        let mut delay = Steps { count: 0, limit: 3 };
+        let _ = breathe.run(&mut max6955, u32::MAX, &mut delay);
+    }
+}
@@ -0,0 +1,72 @@
+//! Countdown timer widget
+
+use super::{Interface, Max6955, TimeFormat};
+
+/// Countdown timer shown as `"MM.SS"`, or `"HH.MM.SS"` from one hour up
+///
+/// Feed it the elapsed time with `tick`, e.g. from a timer interrupt. Seconds are rounded up,
+/// so `"00.00"` appears exactly when the countdown ends.
+pub struct Countdown {
+    remaining_ms: u32,
+}
+
+impl Countdown {
+    /// Create a countdown
+    /// # Arguments
+    /// * `duration_ms` - duration in milliseconds
+    pub fn new(duration_ms: u32) -> Self {
+        Countdown {
+            remaining_ms: duration_ms,
+        }
+    }
+
+    /// Restart with a new duration
+    /// # Arguments
+    /// * `duration_ms` - duration in milliseconds
+    pub fn reset(&mut self, duration_ms: u32) {
+        self.remaining_ms = duration_ms;
+    }
+
+    /// Remaining time in milliseconds
+    pub fn remaining_ms(&self) -> u32 {
+        self.remaining_ms
+    }
+
+    /// `true` once the countdown has ended
+    pub fn is_done(&self) -> bool {
+        self.remaining_ms == 0
+    }
+
+    /// Count down and update the display
+    /// # Arguments
+    /// * `max6955` - display to write to
+    /// * `elapsed_ms` - time since the previous tick in milliseconds
+    ///
+    /// Returns `true` on the tick that reaches zero, and `false` before and after it.
+    pub fn tick<I2C, E>(&mut self, max6955: &mut Max6955<I2C>, elapsed_ms: u32) -> Result<bool, E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let running = self.remaining_ms > 0;
+        self.remaining_ms = self.remaining_ms.saturating_sub(elapsed_ms);
+        self.show(max6955)?;
+        Ok(running && self.remaining_ms == 0)
+    }
+
+    /// Write the remaining time to the display
+    /// # Arguments
+    /// * `max6955` - display to write to
+    pub fn show<I2C, E>(&self, max6955: &mut Max6955<I2C>) -> Result<(), E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let seconds = self.remaining_ms.div_ceil(1000);
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            let hours = hours.min(99) as u8;
+            max6955.write_time(hours, minutes as u8, seconds as u8, TimeFormat::Hms, false)
+        } else {
+            max6955.write_time(minutes as u8, seconds as u8, 0, TimeFormat::Hmm, false)
+        }
+    }
+}
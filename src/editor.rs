@@ -0,0 +1,104 @@
+//! Numeric entry with a blinking digit cursor
+
+use super::{BlinkRate, Interface, Max6955};
+
+/// Key of the numeric entry, e.g. looked up with a `KeyMap`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditKey {
+    /// Increment the digit under edit, `9` wraps to `0`
+    Up,
+    /// Decrement the digit under edit, `0` wraps to `9`
+    Down,
+    /// Move to the next digit on the left
+    Left,
+    /// Move to the next digit on the right
+    Right,
+    /// Finish the entry
+    Enter,
+}
+
+/// Decimal number entry
+///
+/// Shows the value zero padded from text position 0 and blinks the digit under edit.
+/// Feed it keys with `key` until it returns the final value.
+pub struct DigitEditor {
+    digits: [u8; 8],
+    width: usize,
+    position: usize,
+}
+
+impl DigitEditor {
+    /// Start an entry
+    /// # Arguments
+    /// * `value` - initial value. Only the lowest `width` decimal digits are kept.
+    /// * `width` - number of digits `1` ~ `8`
+    pub fn new(mut value: u32, width: usize) -> Self {
+        let width = width.clamp(1, 8);
+        let mut digits: [u8; 8] = [0; 8];
+        for digit in digits[..width].iter_mut().rev() {
+            *digit = (value % 10) as u8;
+            value /= 10;
+        }
+        DigitEditor {
+            digits,
+            width,
+            position: width - 1,
+        }
+    }
+
+    /// Current value
+    pub fn value(&self) -> u32 {
+        self.digits[..self.width]
+            .iter()
+            .fold(0, |value, &digit| value * 10 + digit as u32)
+    }
+
+    /// Text position of the digit under edit
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Write the value and blink the digit under edit
+    /// # Arguments
+    /// * `max6955` - display to write to
+    pub fn show<I2C, E>(&self, max6955: &mut Max6955<I2C>) -> Result<(), E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let mut text: [u8; 8] = [0; 8];
+        for (c, digit) in text.iter_mut().zip(self.digits.iter()) {
+            *c = b'0' + digit;
+        }
+        max6955.write_ascii(&text[..self.width])?;
+        max6955.set_edit_cursor(Some(self.position as u8), BlinkRate::Fast)
+    }
+
+    /// Handle a key and update the display
+    /// # Arguments
+    /// * `max6955` - display to write to
+    /// * `key` - key pressed
+    ///
+    /// Returns the final value on `EditKey::Enter`, which also stops the blinking.
+    pub fn key<I2C, E>(
+        &mut self,
+        max6955: &mut Max6955<I2C>,
+        key: EditKey,
+    ) -> Result<Option<u32>, E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let digit = &mut self.digits[self.position];
+        match key {
+            EditKey::Up => *digit = (*digit + 1) % 10,
+            EditKey::Down => *digit = (*digit + 9) % 10,
+            EditKey::Left => self.position = self.position.saturating_sub(1),
+            EditKey::Right => self.position = (self.position + 1).min(self.width - 1),
+            EditKey::Enter => {
+                max6955.set_edit_cursor(None, BlinkRate::Fast)?;
+                return Ok(Some(self.value()));
+            }
+        }
+        self.show(max6955)?;
+        Ok(None)
+    }
+}
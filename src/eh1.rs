@@ -0,0 +1,41 @@
+//! embedded-hal 1.0 I2C buses
+
+use embedded_hal_1::i2c::I2c;
+use hal::blocking::i2c::{Write, WriteRead};
+
+/// embedded-hal 1.0 `I2c` bus for the driver
+///
+/// Wrap the bus of a HAL built on embedded-hal 1.0 and hand it to `Max6955::new`.
+pub struct I2cEh1<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C: I2c> I2cEh1<I2C> {
+    /// Wrap an embedded-hal 1.0 I2C bus
+    /// # Arguments
+    /// * `i2c` - I2C interface
+    pub fn new(i2c: I2C) -> Self {
+        I2cEh1 { i2c }
+    }
+
+    /// Release the I2C interface
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C: I2c> Write for I2cEh1<I2C> {
+    type Error = I2C::Error;
+
+    fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), I2C::Error> {
+        self.i2c.write(addr, bytes)
+    }
+}
+
+impl<I2C: I2c> WriteRead for I2cEh1<I2C> {
+    type Error = I2C::Error;
+
+    fn write_read(&mut self, addr: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), I2C::Error> {
+        self.i2c.write_read(addr, bytes, buffer)
+    }
+}
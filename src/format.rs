@@ -0,0 +1,337 @@
+//! Number formatting for the numeric write helpers
+
+use super::{ascii, Padding, Separator, DECIMAL_POINT};
+
+/// SI prefixes from pico to tera, no prefix at `UNIT_PREFIX`
+const PREFIXES: [u8; 9] = [b'p', b'n', b'u', b'm', b' ', b'k', b'M', b'G', b'T'];
+const UNIT_PREFIX: usize = 4;
+
+/// Digit register values of a right-aligned field, filled from the right
+pub(crate) struct Field {
+    digits: [u8; 8],
+    width: usize,
+    len: usize,
+    overflow: bool,
+    zeros: usize,
+    separator: Separator,
+}
+
+impl Field {
+    /// Blank field of `width` digits, numbers padded as set by `padding`
+    /// and decimal thousands marked as set by `separator`
+    pub(crate) fn new(width: usize, padding: Padding, separator: Separator) -> Self {
+        Field {
+            digits: [b' '; 8],
+            width,
+            len: 0,
+            overflow: false,
+            zeros: match padding {
+                Padding::Blank => 0,
+                Padding::Zeros(digits) => digits,
+            },
+            separator,
+        }
+    }
+
+    /// Put `byte` left of everything pushed so far. Flags an overflow once the field is full.
+    pub(crate) fn push(&mut self, byte: u8) {
+        if self.len == self.width {
+            self.overflow = true;
+        } else {
+            self.len += 1;
+            self.digits[self.width - self.len] = byte;
+        }
+    }
+
+    /// Push the decimal digits of `value`
+    pub(crate) fn push_u32(&mut self, value: u32) {
+        self.push_fixed(value.into(), 0);
+    }
+
+    /// Push the decimal digits of `value` with a decimal point `decimals` digits from the right.
+    /// `Separator::Dot` is left out when there are decimals, it would look like a second point.
+    pub(crate) fn push_fixed(&mut self, mut value: u64, decimals: usize) {
+        let start = self.len;
+        let separator = match self.separator {
+            Separator::Dot if decimals > 0 => Separator::None,
+            separator => separator,
+        };
+        for _ in 0..decimals {
+            self.push(b'0' + (value % 10) as u8);
+            value /= 10;
+        }
+        let mut dot = if decimals > 0 { DECIMAL_POINT } else { 0 };
+        let mut count = 0;
+        loop {
+            if count > 0 && count % 3 == 0 {
+                match separator {
+                    Separator::None => {}
+                    Separator::Dot => dot = DECIMAL_POINT,
+                    Separator::Apostrophe => self.push(b'\''),
+                }
+            }
+            self.push((b'0' + (value % 10) as u8) | dot);
+            dot = 0;
+            count += 1;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.pad(start);
+    }
+
+    /// Push the decimal digits of `value`, with the minus sign right next to the highest digit
+    pub(crate) fn push_i32(&mut self, value: i32) {
+        self.push_fixed_i32(value, 0);
+    }
+
+    /// Signed `push_fixed`, with the minus sign right next to the highest digit
+    pub(crate) fn push_fixed_i32(&mut self, value: i32, decimals: usize) {
+        self.push_fixed(value.unsigned_abs().into(), decimals);
+        if value < 0 {
+            self.push_sign();
+        }
+    }
+
+    /// Push a minus sign. When the field is full, the sign replaces the leftmost digit,
+    /// so a truncated negative number never shows as positive.
+    fn push_sign(&mut self) {
+        if self.len == self.width {
+            self.overflow = true;
+            if self.width > 0 {
+                self.digits[0] = b'-';
+            }
+        } else {
+            self.push(b'-');
+        }
+    }
+
+    /// Push the last two decimal digits of `value`, with a decimal point after them if `dot`
+    pub(crate) fn push_pair(&mut self, value: u8, dot: bool) {
+        let dot = if dot { DECIMAL_POINT } else { 0 };
+        self.push((b'0' + value % 10) | dot);
+        self.push(b'0' + value / 10 % 10);
+    }
+
+    /// Push the digits of `value` in base `radix`, `2` ~ `16`
+    pub(crate) fn push_radix(&mut self, mut value: u32, radix: u32) {
+        let start = self.len;
+        loop {
+            self.push(b"0123456789ABCDEF"[(value % radix) as usize]);
+            value /= radix;
+            if value == 0 {
+                break;
+            }
+        }
+        self.pad(start);
+    }
+
+    /// Push leading zeros until the number that started at `start` has the padding width
+    fn pad(&mut self, start: usize) {
+        while self.len - start < self.zeros && self.len < self.width {
+            self.push(b'0');
+        }
+    }
+
+    /// Push `prefix` if it fits in front of what was pushed so far, otherwise leave it out
+    pub(crate) fn push_prefix(&mut self, prefix: &[u8]) {
+        if self.width - self.len >= prefix.len() {
+            for &byte in prefix.iter().rev() {
+                self.push(byte);
+            }
+        }
+    }
+
+    /// Push `value` rounded to `decimals` places. Like integers, values that do not fit keep
+    /// their lowest digits. NaN and infinity overflow without pushing anything.
+    pub(crate) fn push_f32(&mut self, value: f32, decimals: usize) {
+        if value.is_nan() || value.is_infinite() {
+            self.overflow = true;
+            return;
+        }
+        let mut scaled = if value < 0.0 { -value } else { value };
+        for _ in 0..decimals {
+            scaled *= 10.0;
+        }
+        let scaled = low_digits(scaled + 0.5);
+        self.push_fixed(scaled, decimals);
+        if value < 0.0 && scaled != 0 {
+            self.push_sign();
+        }
+    }
+
+    /// Push a fixed-point number of `frac_bits` fractional bits, rounded to `decimals` places
+    #[cfg(feature = "fixed")]
+    pub(crate) fn push_fixed_point(&mut self, bits: i32, frac_bits: u32, decimals: usize) {
+        let mut scaled = bits.unsigned_abs() as u64;
+        for _ in 0..decimals {
+            scaled = scaled.saturating_mul(10);
+        }
+        if frac_bits > 0 {
+            scaled = (scaled + (1 << (frac_bits - 1))) >> frac_bits;
+        }
+        self.push_fixed(scaled, decimals);
+        if bits < 0 && scaled != 0 {
+            self.push_sign();
+        }
+    }
+
+    /// Push `value` scaled to `1` ~ `999` with an SI prefix, followed by `unit`.
+    /// As many decimals as fit are shown, up to three.
+    pub(crate) fn push_engineering(&mut self, value: f32, unit: &str) {
+        if value.is_nan() || value.is_infinite() {
+            self.overflow = true;
+            return;
+        }
+        let mut magnitude = if value < 0.0 { -value } else { value };
+        let mut prefix = UNIT_PREFIX;
+        while magnitude >= 1000.0 && prefix < PREFIXES.len() - 1 {
+            magnitude /= 1000.0;
+            prefix += 1;
+        }
+        while magnitude != 0.0 && magnitude < 1.0 && prefix > 0 {
+            magnitude *= 1000.0;
+            prefix -= 1;
+        }
+        let units = unit.chars().count();
+        let mut decimals = 0;
+        // rounding can carry into a fourth integer digit, e.g. 999.96 -> 1.000k
+        for _ in 0..2 {
+            let integers = if magnitude >= 100.0 {
+                3
+            } else if magnitude >= 10.0 {
+                2
+            } else {
+                1
+            };
+            let used = integers + units + (prefix != UNIT_PREFIX) as usize + (value < 0.0) as usize;
+            decimals = self.width.saturating_sub(used).min(3);
+            let mut rounded = magnitude;
+            for _ in 0..decimals {
+                rounded *= 10.0;
+            }
+            let limit = [1000.0, 10000.0, 100000.0, 1000000.0][decimals];
+            if rounded + 0.5 < limit {
+                break;
+            }
+            if prefix == PREFIXES.len() - 1 {
+                // beyond 999T: show the whole number, truncated like an integer
+                decimals = 0;
+                break;
+            }
+            magnitude /= 1000.0;
+            prefix += 1;
+        }
+        for c in unit.chars().rev() {
+            self.push(ascii(c));
+        }
+        if prefix != UNIT_PREFIX {
+            self.push(PREFIXES[prefix]);
+        }
+        let signed = if value < 0.0 { -magnitude } else { magnitude };
+        self.push_f32(signed, decimals);
+    }
+
+    /// Digit register values in text order, and whether anything was dropped
+    pub(crate) fn finish(self) -> ([u8; 8], bool) {
+        (self.digits, self.overflow)
+    }
+}
+
+/// Whole part of a non-negative finite `value`. Beyond the `u64` range only the lowest eight
+/// digits are kept, led by a `1` so that the number still overflows the field.
+fn low_digits(value: f32) -> u64 {
+    const LOW: u64 = 100_000_000;
+    if value < u64::MAX as f32 {
+        return value as u64;
+    }
+    // value = mantissa * 2^exponent, exactly
+    let bits = value.to_bits();
+    let mantissa = (bits & 0x007F_FFFF | 0x0080_0000) as u64;
+    let exponent = (bits >> 23 & 0xFF) as i32 - 150;
+    let mut low = mantissa % LOW;
+    for _ in 0..exponent {
+        low = low * 2 % LOW;
+    }
+    LOW + low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Field;
+    use {Padding, Separator, DECIMAL_POINT};
+
+    fn blank() -> Field {
+        Field::new(8, Padding::Blank, Separator::None)
+    }
+
+    #[test]
+    fn negative_numbers_that_do_not_fit_keep_the_sign() {
+        let mut field = blank();
+        field.push_i32(-123456789);
+        assert_eq!(field.finish(), (*b"-3456789", true));
+        let mut field = blank();
+        field.push_i32(i32::MIN);
+        assert_eq!(field.finish(), (*b"-7483648", true));
+        let mut field = blank();
+        field.push_i32(-1234567);
+        assert_eq!(field.finish(), (*b"-1234567", false));
+    }
+
+    #[test]
+    fn floats_that_do_not_fit_keep_the_lowest_digits() {
+        let mut field = blank();
+        field.push_f32(8589934592.0, 0);
+        assert_eq!(field.finish(), (*b"89934592", true));
+        let mut field = blank();
+        field.push_f32(f32::from_bits((127 + 70) << 23), 0);
+        assert_eq!(field.finish(), (*b"11303424", true));
+        let mut field = blank();
+        field.push_f32(f32::NAN, 1);
+        assert_eq!(field.finish(), (*b"        ", true));
+    }
+
+    #[test]
+    fn floats_round_to_the_decimals() {
+        let mut field = blank();
+        field.push_f32(-2.25, 1);
+        let mut expected = *b"     -23";
+        expected[6] |= DECIMAL_POINT;
+        assert_eq!(field.finish(), (expected, false));
+    }
+
+    #[test]
+    fn dot_separator_is_left_out_with_decimals() {
+        let mut field = Field::new(8, Padding::Blank, Separator::Dot);
+        field.push_u32(1234567);
+        let mut expected = *b" 1234567";
+        expected[1] |= DECIMAL_POINT;
+        expected[4] |= DECIMAL_POINT;
+        assert_eq!(field.finish(), (expected, false));
+        let mut field = Field::new(8, Padding::Blank, Separator::Dot);
+        field.push_fixed(12345, 1);
+        let mut expected = *b"   12345";
+        expected[6] |= DECIMAL_POINT;
+        assert_eq!(field.finish(), (expected, false));
+    }
+
+    #[test]
+    fn engineering_beyond_tera_shows_digits() {
+        let mut field = blank();
+        field.push_engineering(1e20, "Hz");
+        let (digits, overflow) = field.finish();
+        assert!(overflow);
+        assert_eq!(&digits[5..], b"THz");
+        assert!(digits[..5].iter().all(u8::is_ascii_digit));
+    }
+
+    #[test]
+    fn engineering_scales_to_a_prefix() {
+        let mut field = blank();
+        field.push_engineering(1234.0, "Hz");
+        let mut expected = *b" 1234kHz";
+        expected[1] |= DECIMAL_POINT;
+        assert_eq!(field.finish(), (expected, false));
+    }
+}
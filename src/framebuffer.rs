@@ -0,0 +1,93 @@
+//! Off-screen image of both digit planes and the digit intensities
+
+use super::{ascii, Interface, Max6955, Plane, MAX_INTENSITY};
+
+/// Contents of both digit planes and the per-digit intensities, in text order
+///
+/// Compose a frame, then write it to the display with `flush`.
+#[derive(Clone, Copy)]
+pub struct FrameBuffer {
+    planes: [[u8; 8]; 2],
+    intensities: [u8; 8],
+}
+
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameBuffer {
+    /// Create a blank frame at full intensity
+    pub fn new() -> Self {
+        FrameBuffer {
+            planes: [[b' '; 8]; 2],
+            intensities: [MAX_INTENSITY; 8],
+        }
+    }
+
+    /// Put a character
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    /// * `position` - text position `0` ~ `7`
+    /// * `c` - character
+    pub fn set_char(&mut self, plane: Plane, position: usize, c: char) {
+        self.set_digit(plane, position, ascii(c));
+    }
+
+    /// Put a raw digit register value
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    /// * `position` - text position `0` ~ `7`
+    /// * `byte` - digit register value, including the decimal point bit
+    pub fn set_digit(&mut self, plane: Plane, position: usize, byte: u8) {
+        self.planes[plane as usize][position & 0x07] = byte;
+    }
+
+    /// Write Text to a plane, blanking the rest of it
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    /// * `text` - text to write. Characters beyond eight are dropped.
+    pub fn write_str(&mut self, plane: Plane, text: &str) {
+        let mut chars = text.chars();
+        for digit in self.planes[plane as usize].iter_mut() {
+            *digit = chars.next().map_or(b' ', ascii);
+        }
+    }
+
+    /// Set a Digit Intensity
+    /// # Arguments
+    /// * `position` - text position `0` ~ `7`
+    /// * `level` - intensity level `0`: lowest ~ `15`: highest. Higher values are clamped to `15`.
+    pub fn set_intensity(&mut self, position: usize, level: u8) {
+        self.intensities[position & 0x07] = level.min(MAX_INTENSITY);
+    }
+
+    /// Digit register values of a plane
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    pub fn plane(&self, plane: Plane) -> &[u8; 8] {
+        &self.planes[plane as usize]
+    }
+
+    /// Digit intensities
+    pub fn intensities(&self) -> &[u8; 8] {
+        &self.intensities
+    }
+
+    /// Write the frame to the display
+    ///
+    /// Writes each plane and the intensity registers in one transaction each, and selects
+    /// `IntensityMode::PerDigit`. Per-digit blinking is turned off, since the frame sets plane P1.
+    /// # Arguments
+    /// * `max6955` - display to write to
+    pub fn flush<I2C, E>(&self, max6955: &mut Max6955<I2C>) -> Result<(), E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        max6955.blink_mask = 0;
+        max6955.write_digits(&self.planes[Plane::P0 as usize])?;
+        max6955.write_plane(Plane::P1, &self.planes[Plane::P1 as usize])?;
+        max6955.set_digit_intensities(&self.intensities)
+    }
+}
@@ -0,0 +1,144 @@
+//! General purpose I/O on ports P0 ~ P4
+
+use super::{Interface, Max6955, PortConfig, Register};
+
+/// Port bits of the GPIO data register
+pub(crate) const PORTS: u8 = 0x1F;
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Read GPIO
+    ///
+    /// Returns the levels of ports P0 ~ P4 in bits 0 ~ 4.
+    pub fn read_gpio(&mut self) -> Result<u8, E> {
+        Ok(self.read_register(Register::GpioData)? & PORTS)
+    }
+
+    /// Read Port Pin
+    /// # Arguments
+    ///
+    /// * `port` - `0` ~ `4`. Only the lowest three bits are used, and ports `5` ~ `7` are
+    ///   ignored by all port methods.
+    ///
+    /// Returns `true` if the pin is high.
+    pub fn read_pin(&mut self, port: usize) -> Result<bool, E> {
+        Ok(self.read_gpio()? & port_bit(port) != 0)
+    }
+
+    /// Write GPIO
+    ///
+    /// Ports configured as inputs ignore their bits.
+    /// # Arguments
+    ///
+    /// * `value` - levels of ports P0 ~ P4 in bits 0 ~ 4
+    pub fn write_gpio(&mut self, value: u8) -> Result<(), E> {
+        let value = value & PORTS;
+        self.write_register(Register::GpioData, value)?;
+        self.outputs = Some(value);
+        Ok(())
+    }
+
+    /// Set Port Pin
+    ///
+    /// The other output levels are taken from the last GPIO write, read once on first use.
+    /// # Arguments
+    ///
+    /// * `port` - `0` ~ `4`, see `read_pin`
+    /// * `level` - `true`: high, `false`: low
+    pub fn set_pin(&mut self, port: usize, level: bool) -> Result<(), E> {
+        if level {
+            self.modify_gpio(port_bit(port), 0)
+        } else {
+            self.modify_gpio(0, port_bit(port))
+        }
+    }
+
+    /// Change several Port Pins in one write
+    ///
+    /// The other output levels are taken from the last GPIO write, read once on first use.
+    /// Bits in both masks end up set.
+    /// # Arguments
+    ///
+    /// * `set_mask` - ports to drive high, P0 ~ P4 in bits 0 ~ 4
+    /// * `clear_mask` - ports to drive low, P0 ~ P4 in bits 0 ~ 4
+    pub fn modify_gpio(&mut self, set_mask: u8, clear_mask: u8) -> Result<(), E> {
+        let outputs = self.outputs()?;
+        self.write_gpio(outputs & !clear_mask | set_mask)
+    }
+
+    /// Toggle Port Pin
+    ///
+    /// Flips the level last written, read once on first use.
+    /// # Arguments
+    ///
+    /// * `port` - `0` ~ `4`, see `read_pin`
+    pub fn toggle_pin(&mut self, port: usize) -> Result<(), E> {
+        let outputs = self.outputs()?;
+        self.write_gpio(outputs ^ port_bit(port))
+    }
+
+    /// Configure all Port Pins in one write
+    /// # Arguments
+    ///
+    /// * `config` - pin directions, key interrupt output and key scan size
+    pub fn set_port_config(&mut self, config: PortConfig) -> Result<(), E> {
+        self.write_port_config(config.value())
+    }
+
+    /// Read Port Configuration
+    ///
+    /// Returns the pin directions, key interrupt output and key scan size.
+    pub fn port_config(&mut self) -> Result<PortConfig, E> {
+        let port_config = self.read_register(Register::PortConfiguration)?;
+        self.port_config = Some(port_config);
+        Ok(PortConfig::from(port_config))
+    }
+
+    /// Reload the driver's copies of the port configuration and the output levels
+    ///
+    /// Pin changes start from these copies instead of reading the device. Call this when the
+    /// device was configured by someone else, e.g. before the microcontroller restarted.
+    pub fn resync_gpio(&mut self) -> Result<(), E> {
+        self.port_config = Some(self.read_register(Register::PortConfiguration)?);
+        self.outputs = Some(self.read_gpio()?);
+        Ok(())
+    }
+
+    /// Write the port configuration register and keep a copy
+    pub(crate) fn write_port_config(&mut self, port_config: u8) -> Result<(), E> {
+        self.write_register(Register::PortConfiguration, port_config)?;
+        self.port_config = Some(port_config);
+        Ok(())
+    }
+
+    /// Output levels from the driver's copy, read from the device the first time
+    fn outputs(&mut self) -> Result<u8, E> {
+        match self.outputs {
+            Some(outputs) => Ok(outputs),
+            None => {
+                let outputs = self.read_gpio()?;
+                self.outputs = Some(outputs);
+                Ok(outputs)
+            }
+        }
+    }
+
+    /// Port configuration from the driver's copy, read from the device the first time
+    pub(crate) fn port_config_bits(&mut self) -> Result<u8, E> {
+        match self.port_config {
+            Some(port_config) => Ok(port_config),
+            None => {
+                let port_config = self.read_register(Register::PortConfiguration)?;
+                self.port_config = Some(port_config);
+                Ok(port_config)
+            }
+        }
+    }
+}
+
+/// Bit of port `port` in the GPIO and port configuration registers, `0` for ports `5` ~ `7`
+pub(crate) fn port_bit(port: usize) -> u8 {
+    (1 << (port & 0x07)) & PORTS
+}
@@ -0,0 +1,261 @@
+//! Per-digit intensity control
+
+use super::{
+    BrightnessCurve, Calibration, ConfigBitFlag, IntensityBank, IntensityMode, Interface, Max6955,
+    Register, MAX_INTENSITY,
+};
+use bit_field::BitField;
+use hal::blocking::delay::DelayMs;
+
+/// Time share of each step from level `l` to `l + 1` in percent for a perceptually even fade
+/// with `BrightnessCurve::Gamma`. The average is 100.
+const GAMMA_STEP_WEIGHTS: [u32; 15] = [
+    387, 160, 123, 104, 91, 83, 76, 71, 66, 63, 60, 57, 55, 53, 51,
+];
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Select Global or Per-Digit Intensity
+    /// # Arguments
+    ///
+    /// * `mode` - `IntensityMode::Global`: the global intensity register applies to all digits,
+    ///   `IntensityMode::PerDigit`: the digit intensity registers apply
+    pub fn set_intensity_mode(&mut self, mode: IntensityMode) -> Result<(), E> {
+        self.set_configuration_bit(ConfigBitFlag::Intensity, mode.value())
+    }
+
+    /// Set Digit Intensity
+    ///
+    /// Switches to `IntensityMode::PerDigit` if needed.
+    /// # Arguments
+    ///
+    /// * `digit` - text position `0` ~ `7`
+    /// * `level` - intensity level `0`: lowest ~ `15`: highest. Higher values are clamped to `15`.
+    pub fn set_digit_intensity(&mut self, digit: u8, level: u8) -> Result<(), E> {
+        self.set_bank_digit_intensity(IntensityBank::Primary, digit, level)
+    }
+
+    /// Set All Digit Intensities in one transaction
+    ///
+    /// Switches to `IntensityMode::PerDigit` if needed.
+    /// # Arguments
+    ///
+    /// * `levels` - intensity level `0` ~ `15` of each digit in text order
+    pub fn set_digit_intensities(&mut self, levels: &[u8; 8]) -> Result<(), E> {
+        self.set_bank_digit_intensities(IntensityBank::Primary, levels)
+    }
+
+    /// Set Digit Intensity in either intensity register bank
+    ///
+    /// Switches to `IntensityMode::PerDigit` if needed.
+    /// # Arguments
+    ///
+    /// * `bank` - `IntensityBank::Primary` or `IntensityBank::Secondary`
+    /// * `digit` - text position `0` ~ `7`
+    /// * `level` - intensity level `0`: lowest ~ `15`: highest. Higher values are clamped to `15`.
+    ///   The calibration is applied before writing.
+    pub fn set_bank_digit_intensity(
+        &mut self,
+        bank: IntensityBank,
+        digit: u8,
+        level: u8,
+    ) -> Result<(), E> {
+        let digit = self.physical_digit(digit as usize);
+        let reg = Register::intensity(bank, digit);
+        let mut levels: u8 = self.read_register(reg)?;
+        levels.set_bits(
+            nibble(digit),
+            self.calibration.apply(level.min(MAX_INTENSITY)),
+        );
+        self.write_register(reg, levels)?;
+        self.use_intensity_mode(IntensityMode::PerDigit)
+    }
+
+    /// Set All Digit Intensities of an intensity register bank in one transaction
+    ///
+    /// Switches to `IntensityMode::PerDigit` if needed.
+    /// # Arguments
+    ///
+    /// * `bank` - `IntensityBank::Primary` or `IntensityBank::Secondary`
+    /// * `levels` - intensity level `0` ~ `15` of each digit in text order
+    pub fn set_bank_digit_intensities(
+        &mut self,
+        bank: IntensityBank,
+        levels: &[u8; 8],
+    ) -> Result<(), E> {
+        let mut registers: [u8; 4] = [0; 4];
+        for (position, &level) in levels.iter().enumerate() {
+            let digit = self.physical_digit(position);
+            let level = self.calibration.apply(level.min(MAX_INTENSITY));
+            registers[digit / 2].set_bits(nibble(digit), level);
+        }
+        self.write_registers(Register::intensity(bank, 0), &registers)?;
+        self.use_intensity_mode(IntensityMode::PerDigit)
+    }
+
+    /// Read Global Intensity
+    ///
+    /// Returns the level before calibration, so it can be passed back to
+    /// `set_global_intensity`, e.g. one step up or down.
+    pub fn global_intensity(&mut self) -> Result<u8, E> {
+        let calibrated = self.read_register(Register::GlobalIntensity)? & MAX_INTENSITY;
+        if self.calibration.apply(self.intensity) == calibrated {
+            Ok(self.intensity)
+        } else {
+            Ok(self.calibration.level(calibrated))
+        }
+    }
+
+    /// Read Digit Intensity
+    ///
+    /// Returns the level before calibration, so it can be passed back to `set_digit_intensity`.
+    /// # Arguments
+    ///
+    /// * `digit` - text position `0` ~ `7`
+    pub fn digit_intensity(&mut self, digit: u8) -> Result<u8, E> {
+        let digit = self.physical_digit(digit as usize);
+        let levels: u8 = self.read_register(Register::intensity(IntensityBank::Primary, digit))?;
+        Ok(self.calibration.level(levels.get_bits(nibble(digit))))
+    }
+
+    /// Highlight Digit
+    /// # Arguments
+    ///
+    /// * `digit` - text position `0` ~ `7`
+    /// * `on` - `true`: show the digit at full intensity and the others one level below the
+    ///   global intensity, `false`: return to the global intensity
+    pub fn highlight_digit(&mut self, digit: u8, on: bool) -> Result<(), E> {
+        if on {
+            let mut levels = [self.intensity.saturating_sub(1); 8];
+            levels[(digit & 0x07) as usize] = MAX_INTENSITY;
+            self.set_digit_intensities(&levels)
+        } else {
+            self.use_intensity_mode(IntensityMode::Global)
+        }
+    }
+
+    /// Set Global Intensity in percent
+    /// # Arguments
+    ///
+    /// * `percent` - brightness `0` ~ `100`, mapped onto the 16 intensity levels along the brightness curve
+    pub fn set_intensity_percent(&mut self, percent: u8) -> Result<(), E> {
+        self.set_global_intensity(percent_to_level(percent, self.curve))
+    }
+
+    /// Set Intensity Calibration
+    ///
+    /// Applies to all later global and digit intensity writes of this device.
+    /// # Arguments
+    ///
+    /// * `calibration` - scale and offset, `Calibration::NONE` to turn it off
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Set the brightness curve of the percent and fade APIs
+    /// # Arguments
+    ///
+    /// * `curve` - `BrightnessCurve::Linear` or `BrightnessCurve::Gamma`
+    pub fn set_brightness_curve(&mut self, curve: BrightnessCurve) {
+        self.curve = curve;
+    }
+
+    /// Fade Global Intensity
+    ///
+    /// Steps the global intensity one level at a time from the last level set to `target`.
+    /// With `BrightnessCurve::Gamma` the dim levels are held longer, so the fade looks even.
+    /// # Arguments
+    ///
+    /// * `target` - intensity level `0`: lowest ~ `15`: highest
+    /// * `step_ms` - average time per level in milliseconds
+    /// * `delay` - delay provider
+    pub fn fade_to<D: DelayMs<u32>>(
+        &mut self,
+        target: u8,
+        step_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), E> {
+        let target = target.min(MAX_INTENSITY);
+        while self.intensity != target {
+            let next = if self.intensity < target {
+                self.intensity + 1
+            } else {
+                self.intensity - 1
+            };
+            let step_ms = match self.curve {
+                BrightnessCurve::Linear => step_ms,
+                BrightnessCurve::Gamma => {
+                    let lower = self.intensity.min(next) as usize;
+                    (step_ms as u64 * GAMMA_STEP_WEIGHTS[lower] as u64 / 100).min(u32::MAX as u64)
+                        as u32
+                }
+            };
+            delay.delay_ms(step_ms);
+            self.set_global_intensity(next)?;
+        }
+        Ok(())
+    }
+
+    /// Switch to `mode` unless it is already selected
+    ///
+    /// The selected mode comes from the driver's copy of the configuration, read once on first use.
+    pub(crate) fn use_intensity_mode(&mut self, mode: IntensityMode) -> Result<(), E> {
+        self.config()?;
+        if self.intensity_mode == mode {
+            Ok(())
+        } else {
+            self.set_intensity_mode(mode)
+        }
+    }
+}
+
+/// Nearest intensity level for `percent`
+fn percent_to_level(percent: u8, curve: BrightnessCurve) -> u8 {
+    let percent = percent.min(100) as u32;
+    let max = MAX_INTENSITY as u32;
+    let level = match curve {
+        BrightnessCurve::Linear => (percent * max + 50) / 100,
+        BrightnessCurve::Gamma => (percent * percent * max + 5000) / 10000,
+    };
+    level as u8
+}
+
+/// Bits of digit `digit` in its intensity register. Odd digits use the upper nibble.
+fn nibble(digit: usize) -> core::ops::Range<usize> {
+    if digit & 1 == 0 {
+        0..4
+    } else {
+        4..8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hal::blocking::delay::DelayMs;
+    use mock::Mock;
+    use {BrightnessCurve, Max6955, Register};
+
+    /// Delay that keeps the longest requested wait
+    struct Longest(u32);
+
+    impl DelayMs<u32> for Longest {
+        fn delay_ms(&mut self, ms: u32) {
+            self.0 = self.0.max(ms);
+        }
+    }
+
+    #[test]
+    fn gamma_fade_saturates_long_steps() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        max6955.set_brightness_curve(BrightnessCurve::Gamma);
+        let mut delay = Longest(0);
+        max6955.fade_to(2, u32::MAX, &mut delay).unwrap();
+        assert_eq!(delay.0, u32::MAX);
+        assert_eq!(max6955.i2c.get(Register::GlobalIntensity), 2);
+        let mut delay = Longest(0);
+        max6955.fade_to(3, 1_000_000_000, &mut delay).unwrap();
+        assert_eq!(delay.0, 1_230_000_000);
+    }
+}
@@ -0,0 +1,204 @@
+//! Register access over I2C (MAX6955) or SPI (MAX6954)
+
+use hal::blocking::i2c::{Write, WriteRead};
+use hal::blocking::spi::Transfer;
+use hal::digital::v2::OutputPin;
+
+/// Read bit of a MAX6954 command word
+const SPI_READ: u8 = 0x80;
+
+/// No-op register of the MAX6954, clocks out the data of a read
+const SPI_NO_OP: u8 = 0x00;
+
+/// Bus that reaches the registers of the display driver
+///
+/// Implemented for every embedded-hal I2C bus and for `SpiInterface`. Implement it on a mock
+/// to test code that uses the driver without hardware.
+pub trait Interface {
+    /// Bus error
+    type Error;
+
+    /// Write consecutive registers
+    /// # Arguments
+    ///
+    /// * `addr` - I2C address, ignored on SPI
+    /// * `reg` - first register
+    /// * `values` - register values, at most eight
+    fn write_registers(&mut self, addr: u8, reg: u8, values: &[u8]) -> Result<(), Self::Error>;
+
+    /// Read consecutive registers
+    /// # Arguments
+    ///
+    /// * `addr` - I2C address, ignored on SPI
+    /// * `reg` - first register
+    /// * `buffer` - register values
+    fn read_registers(&mut self, addr: u8, reg: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+impl<I2C, E> Interface for I2C
+where
+    I2C: WriteRead<Error = E> + Write<Error = E>,
+{
+    type Error = E;
+
+    fn write_registers(&mut self, addr: u8, reg: u8, values: &[u8]) -> Result<(), E> {
+        let len = values.len().min(8);
+        let mut row: [u8; 9] = [0; 9];
+        row[0] = reg;
+        row[1..=len].copy_from_slice(&values[..len]);
+        Write::write(self, addr, &row[..=len])
+    }
+
+    fn read_registers(&mut self, addr: u8, reg: u8, buffer: &mut [u8]) -> Result<(), E> {
+        self.write_read(addr, &[reg], buffer)
+    }
+}
+
+/// Error of `SpiInterface`
+#[derive(Debug)]
+pub enum SpiError<SE, PE> {
+    /// SPI bus error
+    Spi(SE),
+    /// Chip select pin error
+    Pin(PE),
+}
+
+/// SPI bus of a MAX6954
+///
+/// Each register is one 16-bit frame framed by `cs`: a read bit, the register address and the
+/// data. The MAX6954 shares its register map with the MAX6955, so the whole driver works
+/// through `Max6955::new(SpiInterface::new(spi, cs))`. The SPI bus must run in mode 0.
+pub struct SpiInterface<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> SpiInterface<SPI, CS> {
+    /// Wrap an SPI bus and its chip select
+    /// # Arguments
+    /// * `spi` - SPI interface
+    /// * `cs` - chip select pin, active low
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        SpiInterface { spi, cs }
+    }
+
+    /// Release the SPI interface and the chip select pin
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+}
+
+impl<SPI, CS, SE, PE> SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = SE>,
+    CS: OutputPin<Error = PE>,
+{
+    /// Send one frame and keep what was clocked in
+    fn frame(&mut self, words: &mut [u8; 2]) -> Result<(), SpiError<SE, PE>> {
+        self.cs.set_low().map_err(SpiError::Pin)?;
+        let result = self.spi.transfer(words).map(|_| ());
+        self.cs.set_high().map_err(SpiError::Pin)?;
+        result.map_err(SpiError::Spi)
+    }
+}
+
+impl<SPI, CS, SE, PE> Interface for SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = SE>,
+    CS: OutputPin<Error = PE>,
+{
+    type Error = SpiError<SE, PE>;
+
+    fn write_registers(&mut self, _addr: u8, reg: u8, values: &[u8]) -> Result<(), Self::Error> {
+        for (i, &value) in values.iter().take(8).enumerate() {
+            self.frame(&mut [reg + i as u8, value])?;
+        }
+        Ok(())
+    }
+
+    fn read_registers(&mut self, _addr: u8, reg: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        for (i, value) in buffer.iter_mut().enumerate() {
+            self.frame(&mut [SPI_READ | (reg + i as u8), 0])?;
+            let mut words = [SPI_NO_OP, 0];
+            self.frame(&mut words)?;
+            *value = words[1];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interface, SpiInterface};
+    use hal::blocking::spi::Transfer;
+    use hal::digital::v2::OutputPin;
+
+    /// SPI bus that logs frames and answers a read with `0x40 | register`
+    #[derive(Default)]
+    struct Bus {
+        frames: [[u8; 2]; 8],
+        count: usize,
+        pending: u8,
+    }
+
+    impl Transfer<u8> for Bus {
+        type Error = ();
+
+        fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], ()> {
+            self.frames[self.count].copy_from_slice(words);
+            self.count += 1;
+            let reply = self.pending;
+            self.pending = if words[0] & 0x80 != 0 {
+                0x40 | (words[0] & 0x7F)
+            } else {
+                0
+            };
+            words[1] = reply;
+            Ok(words)
+        }
+    }
+
+    /// Chip select that counts its edges
+    #[derive(Default)]
+    struct Cs {
+        low: usize,
+        high: usize,
+    }
+
+    impl OutputPin for Cs {
+        type Error = ();
+
+        fn set_low(&mut self) -> Result<(), ()> {
+            self.low += 1;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), ()> {
+            self.high += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_send_one_frame_per_register() {
+        let mut spi = SpiInterface::new(Bus::default(), Cs::default());
+        spi.write_registers(0x60, 0x20, &[0x41, 0x42]).unwrap();
+        let (bus, cs) = spi.release();
+        assert_eq!(&bus.frames[..bus.count], &[[0x20, 0x41], [0x21, 0x42]]);
+        assert_eq!((cs.low, cs.high), (2, 2));
+    }
+
+    #[test]
+    fn reads_clock_the_data_out_with_a_no_op() {
+        let mut spi = SpiInterface::new(Bus::default(), Cs::default());
+        let mut buffer = [0; 2];
+        spi.read_registers(0x60, 0x08, &mut buffer).unwrap();
+        assert_eq!(buffer, [0x48, 0x49]);
+        let (bus, cs) = spi.release();
+        assert_eq!(
+            &bus.frames[..bus.count],
+            &[[0x88, 0], [0x00, 0], [0x89, 0], [0x00, 0]]
+        );
+        assert_eq!((cs.low, cs.high), (4, 4));
+    }
+}
@@ -0,0 +1,259 @@
+//! Key press, release and repeat detection
+
+use super::{Interface, Max6955};
+#[cfg(feature = "heapless")]
+use heapless::spsc::Producer;
+
+/// What happened to a key
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyEventKind {
+    Pressed,
+    Released,
+    /// Key still held, see `KeyRepeat`
+    Repeat,
+}
+
+/// Change of one key
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyEvent {
+    /// Key number, key A0 is `0` ~ key D7 is `31`
+    pub key: u8,
+    /// Press or release
+    pub kind: KeyEventKind,
+}
+
+/// Keeps the previous key state and reports the keys that changed since
+pub struct Keypad {
+    keys: u32,
+}
+
+impl Default for Keypad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keypad {
+    /// Create a keypad with all keys up
+    pub fn new() -> Self {
+        Keypad { keys: 0 }
+    }
+
+    /// Keys down at the last poll, key A0 in bit 0 ~ key D7 in bit 31
+    pub fn keys(&self) -> u32 {
+        self.keys
+    }
+
+    /// Read the pressed keys and report the changes
+    /// # Arguments
+    /// * `max6955` - device to read
+    pub fn poll<I2C, E>(&mut self, max6955: &mut Max6955<I2C>) -> Result<KeyEvents, E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let keys = max6955.read_keys()?;
+        Ok(self.update(keys))
+    }
+
+    /// Read the pressed keys and queue the changes
+    ///
+    /// Call it from the key interrupt or a timer, and drain the queue with its `Consumer`
+    /// in the main loop. Returns the number of events dropped because the queue was full.
+    /// # Arguments
+    /// * `max6955` - device to read
+    /// * `queue` - producer end of the event queue
+    #[cfg(feature = "heapless")]
+    pub fn poll_keys<I2C, E, const N: usize>(
+        &mut self,
+        max6955: &mut Max6955<I2C>,
+        queue: &mut Producer<'_, KeyEvent, N>,
+    ) -> Result<usize, E>
+    where
+        I2C: Interface<Error = E>,
+    {
+        let dropped = self
+            .poll(max6955)?
+            .filter(|&event| queue.enqueue(event).is_err())
+            .count();
+        Ok(dropped)
+    }
+
+    /// Report the changes to a key state read elsewhere
+    /// # Arguments
+    /// * `keys` - keys down, key A0 in bit 0 ~ key D7 in bit 31
+    pub fn update(&mut self, keys: u32) -> KeyEvents {
+        let events = KeyEvents {
+            pressed: keys & !self.keys,
+            released: self.keys & !keys,
+        };
+        self.keys = keys;
+        events
+    }
+}
+
+/// Key events of one poll, presses first, each in key order
+pub struct KeyEvents {
+    pressed: u32,
+    released: u32,
+}
+
+impl Iterator for KeyEvents {
+    type Item = KeyEvent;
+
+    fn next(&mut self) -> Option<KeyEvent> {
+        let (keys, kind) = if self.pressed != 0 {
+            (&mut self.pressed, KeyEventKind::Pressed)
+        } else if self.released != 0 {
+            (&mut self.released, KeyEventKind::Released)
+        } else {
+            return None;
+        };
+        let key = keys.trailing_zeros() as u8;
+        *keys &= *keys - 1;
+        Some(KeyEvent { key, kind })
+    }
+}
+
+/// Generates repeat events for a held key
+///
+/// Feed it the events of a `Keypad` and poll it with a millisecond tick count, e.g. from a
+/// monotonic timer. The tick count may wrap around.
+pub struct KeyRepeat {
+    delay_ms: u32,
+    period_ms: u32,
+    key: Option<u8>,
+    next_ms: u32,
+}
+
+impl KeyRepeat {
+    /// Create a key repeat
+    /// # Arguments
+    /// * `delay_ms` - hold time before the first repeat in milliseconds
+    /// * `period_ms` - time between repeats in milliseconds
+    pub fn new(delay_ms: u32, period_ms: u32) -> Self {
+        KeyRepeat {
+            delay_ms,
+            period_ms,
+            key: None,
+            next_ms: 0,
+        }
+    }
+
+    /// Track a key event. The last key pressed repeats until it is released.
+    /// # Arguments
+    /// * `event` - event from `Keypad`
+    /// * `now_ms` - current tick count in milliseconds
+    pub fn event(&mut self, event: KeyEvent, now_ms: u32) {
+        match event.kind {
+            KeyEventKind::Pressed => {
+                self.key = Some(event.key);
+                self.next_ms = now_ms.wrapping_add(self.delay_ms);
+            }
+            KeyEventKind::Released if self.key == Some(event.key) => self.key = None,
+            _ => {}
+        }
+    }
+
+    /// Return a repeat event if one is due
+    /// # Arguments
+    /// * `now_ms` - current tick count in milliseconds
+    pub fn poll(&mut self, now_ms: u32) -> Option<KeyEvent> {
+        let key = self.key?;
+        if (now_ms.wrapping_sub(self.next_ms) as i32) < 0 {
+            return None;
+        }
+        self.next_ms = self.next_ms.wrapping_add(self.period_ms.max(1));
+        Some(KeyEvent {
+            key,
+            kind: KeyEventKind::Repeat,
+        })
+    }
+}
+
+/// Application key codes of the 32 keys, one row of eight per key bank
+///
+/// `T` is anything small to copy, e.g. a `char` or an application enum.
+#[derive(Clone, Copy)]
+pub struct KeyMap<T: Copy> {
+    table: [[T; 8]; 4],
+}
+
+impl<T: Copy> KeyMap<T> {
+    /// Create a key map
+    /// # Arguments
+    /// * `table` - key codes of keys A0 ~ A7, B0 ~ B7, C0 ~ C7 and D0 ~ D7
+    pub const fn new(table: [[T; 8]; 4]) -> Self {
+        KeyMap { table }
+    }
+
+    /// Key code of a key
+    /// # Arguments
+    /// * `key` - key number `0` ~ `31`
+    pub fn code(&self, key: u8) -> T {
+        let key = (key & 0x1F) as usize;
+        self.table[key / 8][key % 8]
+    }
+
+    /// Key code and kind of an event
+    /// # Arguments
+    /// * `event` - event from `Keypad` or `KeyRepeat`
+    pub fn map(&self, event: KeyEvent) -> (T, KeyEventKind) {
+        (self.code(event.key), event.kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyEvent, KeyEventKind, KeyRepeat, Keypad};
+
+    fn event(key: u8, kind: KeyEventKind) -> Option<KeyEvent> {
+        Some(KeyEvent { key, kind })
+    }
+
+    #[test]
+    fn events_report_presses_before_releases_in_key_order() {
+        let mut keypad = Keypad::new();
+        let mut events = keypad.update(0x8000_0001);
+        assert_eq!(events.next(), event(0, KeyEventKind::Pressed));
+        assert_eq!(events.next(), event(31, KeyEventKind::Pressed));
+        assert_eq!(events.next(), None);
+        let mut events = keypad.update(0x0000_0102);
+        assert_eq!(events.next(), event(1, KeyEventKind::Pressed));
+        assert_eq!(events.next(), event(8, KeyEventKind::Pressed));
+        assert_eq!(events.next(), event(0, KeyEventKind::Released));
+        assert_eq!(events.next(), event(31, KeyEventKind::Released));
+        assert_eq!(events.next(), None);
+        assert_eq!(keypad.update(0x0000_0102).count(), 0);
+    }
+
+    #[test]
+    fn repeats_survive_tick_wraparound() {
+        let mut repeat = KeyRepeat::new(500, 100);
+        let start = u32::MAX - 200;
+        repeat.event(
+            KeyEvent {
+                key: 3,
+                kind: KeyEventKind::Pressed,
+            },
+            start,
+        );
+        assert_eq!(repeat.poll(start.wrapping_add(499)), None);
+        assert_eq!(
+            repeat.poll(start.wrapping_add(500)),
+            event(3, KeyEventKind::Repeat)
+        );
+        assert_eq!(repeat.poll(start.wrapping_add(599)), None);
+        assert_eq!(
+            repeat.poll(start.wrapping_add(600)),
+            event(3, KeyEventKind::Repeat)
+        );
+        repeat.event(
+            KeyEvent {
+                key: 3,
+                kind: KeyEventKind::Released,
+            },
+            400,
+        );
+        assert_eq!(repeat.poll(1000), None);
+    }
+}
@@ -0,0 +1,203 @@
+//! Key scanning
+
+use super::{Interface, KeyBank, KeyInterrupt, KeyWaitError, KeyscanConfig, Max6955, Register};
+use bit_field::BitField;
+use hal::blocking::delay::DelayMs;
+use hal::digital::v2::InputPin;
+
+/// Key scan bits of the port configuration register, number of key banks or `0` for none.
+/// Port P0 drives the key interrupt while keys are scanned.
+pub(crate) const KEYSCAN_SIZE: core::ops::Range<usize> = 5..8;
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Read Pressed Keys
+    ///
+    /// Reads the four key pressed registers in one transaction.
+    /// Returns the keys down at the moment, key A0 in bit 0 ~ key D7 in bit 31.
+    pub fn read_keys(&mut self) -> Result<u32, E> {
+        let mut keys: [u8; 4] = [0; 4];
+        self.read_registers(Register::KEY_A_PRESSED, &mut keys)?;
+        Ok(u32::from_le_bytes(keys))
+    }
+
+    /// Read Debounced Keys
+    ///
+    /// Reads the four key debounce registers in one transaction, which clears the key interrupt.
+    /// Returns the masked keys pressed since the previous read, key A0 in bit 0 ~ key D7 in bit 31.
+    pub fn read_debounced_keys(&mut self) -> Result<u32, E> {
+        let mut keys: [u8; 4] = [0; 4];
+        self.read_registers(Register::KeyAMaskDebounce, &mut keys)?;
+        Ok(u32::from_le_bytes(keys))
+    }
+
+    /// Read the Keys behind a Key Interrupt
+    ///
+    /// Reads the key debounce registers A to D in one transaction, which captures the keys
+    /// that raised the interrupt and clears it, and then the key pressed registers. A press
+    /// that arrives between the two reads raises a new interrupt instead of being lost.
+    pub fn read_key_interrupt(&mut self) -> Result<KeyInterrupt, E> {
+        let debounced = self.read_debounced_keys()?;
+        let pressed = self.read_keys()?;
+        Ok(KeyInterrupt { debounced, pressed })
+    }
+
+    /// Wait for a Key Press
+    ///
+    /// Polls the IRQ line until it is asserted, then returns the debounced keys, see
+    /// `read_debounced_keys`. The key interrupt must be enabled with `enable_key_interrupt`.
+    /// Returns `None` if no key was pressed within `timeout_ms`.
+    /// # Arguments
+    ///
+    /// * `irq` - input pin wired to port P0
+    /// * `poll_ms` - polling interval in milliseconds
+    /// * `timeout_ms` - time to wait at most in milliseconds
+    /// * `delay` - delay provider
+    pub fn wait_for_key<P: InputPin<Error = PE>, PE, D: DelayMs<u32>>(
+        &mut self,
+        irq: &P,
+        poll_ms: u32,
+        timeout_ms: u32,
+        delay: &mut D,
+    ) -> Result<Option<u32>, KeyWaitError<E, PE>> {
+        let mut waited: u32 = 0;
+        loop {
+            if irq.is_low().map_err(KeyWaitError::Pin)? {
+                let keys = self.read_debounced_keys().map_err(KeyWaitError::Bus)?;
+                if keys != 0 {
+                    return Ok(Some(keys));
+                }
+            }
+            if waited >= timeout_ms {
+                return Ok(None);
+            }
+            delay.delay_ms(poll_ms);
+            waited = waited.saturating_add(poll_ms.max(1));
+        }
+    }
+
+    /// Set Key Mask
+    ///
+    /// Masked keys are debounced and generate an interrupt when pressed.
+    /// # Arguments
+    ///
+    /// * `bank` - `KeyBank::A` ~ `KeyBank::D`
+    /// * `mask` - bit `n` set: key `n` of the bank is enabled
+    pub fn set_key_mask(&mut self, bank: KeyBank, mask: u8) -> Result<(), E> {
+        self.write_register(bank.mask_register(), mask)?;
+        let bits = bank as usize * 8;
+        self.key_masks.set_bits(bits..bits + 8, mask as u32);
+        Ok(())
+    }
+
+    /// Set All Key Masks in one transaction
+    /// # Arguments
+    ///
+    /// * `masks` - bit `n` set: key `n` is enabled, key A0 in bit 0 ~ key D7 in bit 31
+    pub fn set_key_masks(&mut self, masks: u32) -> Result<(), E> {
+        self.write_registers(Register::KeyAMaskDebounce, &masks.to_le_bytes())?;
+        self.key_masks = masks;
+        Ok(())
+    }
+
+    /// Key Mask of a bank as last set
+    ///
+    /// The mask registers read back debounced keys, so the masks are kept by the driver.
+    /// # Arguments
+    ///
+    /// * `bank` - `KeyBank::A` ~ `KeyBank::D`
+    pub fn key_mask(&self, bank: KeyBank) -> u8 {
+        let bits = bank as usize * 8;
+        self.key_masks.get_bits(bits..bits + 8) as u8
+    }
+
+    /// All Key Masks as last set, key A0 in bit 0 ~ key D7 in bit 31
+    ///
+    /// Keep the value as a snapshot and hand it to `set_key_masks` to put the masks back.
+    pub fn key_masks(&self) -> u32 {
+        self.key_masks
+    }
+
+    /// Rewrite the Key Masks as last set, e.g. after the device lost power
+    pub fn restore_key_masks(&mut self) -> Result<(), E> {
+        self.set_key_masks(self.key_masks)
+    }
+
+    /// Enable the Key Interrupt Output
+    ///
+    /// Port P0 becomes an active-low IRQ output, asserted while a debounced key press is
+    /// waiting to be read from the key debounce registers. The device drives the IRQ output
+    /// exactly while keys are scanned, so enabling it starts scanning keys A0 ~ A7 unless
+    /// keys are scanned already, and disabling it stops key scanning.
+    /// # Arguments
+    ///
+    /// * `enable` - `true`: P0 is the IRQ output, `false`: P0 is a GPIO port
+    pub fn enable_key_interrupt(&mut self, enable: bool) -> Result<(), E> {
+        let mut port_config = self.port_config_bits()?;
+        let banks = port_config.get_bits(KEYSCAN_SIZE);
+        let banks = match (enable, banks) {
+            (false, _) => 0,
+            (true, 0) => KeyscanConfig::Keys8.banks(),
+            (true, banks) => banks,
+        };
+        port_config.set_bits(KEYSCAN_SIZE, banks);
+        self.write_port_config(port_config)
+    }
+
+    /// Configure Key Scanning
+    ///
+    /// Selects the number of scanned key banks in the port configuration register and
+    /// enables all of their keys in the key masks. `KeyscanConfig::Disabled` hands ports
+    /// P0 ~ P4 back to GPIO.
+    /// # Arguments
+    ///
+    /// * `config` - `KeyscanConfig::Disabled`, `KeyscanConfig::Keys8` ~ `KeyscanConfig::Keys32`
+    pub fn set_keyscan(&mut self, config: KeyscanConfig) -> Result<(), E> {
+        let mut port_config = self.port_config_bits()?;
+        port_config.set_bits(KEYSCAN_SIZE, config.banks());
+        self.write_port_config(port_config)?;
+        self.set_key_masks(config.mask())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InputPin;
+    use mock::{Mock, NoDelay};
+    use {KeyWaitError, Max6955, Register};
+
+    /// IRQ line stuck at one level, or failing to read
+    struct Irq(Result<bool, ()>);
+
+    impl InputPin for Irq {
+        type Error = ();
+
+        fn is_high(&self) -> Result<bool, ()> {
+            self.0
+        }
+
+        fn is_low(&self) -> Result<bool, ()> {
+            self.0.map(|high| !high)
+        }
+    }
+
+    #[test]
+    fn wait_for_key_times_out_and_reports_pin_errors() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        assert!(matches!(
+            max6955.wait_for_key(&Irq(Ok(true)), 10, 100, &mut NoDelay),
+            Ok(None)
+        ));
+        assert!(matches!(
+            max6955.wait_for_key(&Irq(Err(())), 10, 100, &mut NoDelay),
+            Err(KeyWaitError::Pin(()))
+        ));
+        max6955.i2c.registers[Register::KeyAMaskDebounce.addr() as usize] = 0x04;
+        assert!(matches!(
+            max6955.wait_for_key(&Irq(Ok(false)), 10, 100, &mut NoDelay),
+            Ok(Some(0x04))
+        ));
+    }
+}
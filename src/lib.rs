@@ -1,11 +1,13 @@
 //! A platform agnostic driver to interface with MAX6955 LED Display Driver
 //!
-//! This driver was built using [`embedded-hal`] traits.
+//! This driver was built using [`embedded-hal`] traits. It talks to a MAX6955 over I2C or,
+//! through `SpiInterface`, to its SPI sibling MAX6954.
 //!
 //! [`embedded-hal`]: https://docs.rs/embedded-hal/~0.2
 //!
 //! ### Datasheets
 //! - [MAX6955](https://datasheets.maximintegrated.com/en/ds/MAX6955.pdf)
+//! - [MAX6954](https://datasheets.maximintegrated.com/en/ds/MAX6954.pdf)
 //!
 
 // #![deny(missing_docs)]
@@ -14,39 +16,158 @@
 
 extern crate bit_field;
 extern crate embedded_hal as hal;
+#[cfg(feature = "eh-1")]
+extern crate embedded_hal_1;
+#[cfg(feature = "fixed")]
+extern crate fixed;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "ufmt")]
+extern crate ufmt;
+
+mod address;
+mod blink;
+mod breathe;
+mod countdown;
+mod editor;
+#[cfg(feature = "eh-1")]
+mod eh1;
+mod format;
+mod framebuffer;
+mod gpio;
+mod intensity;
+mod interface;
+mod keypad;
+mod keys;
+#[cfg(test)]
+mod mock;
+mod multi;
+mod pins;
+mod sized;
+mod split;
+mod state;
+mod typestate;
+#[cfg(feature = "ufmt")]
+mod writer;
+
+pub use address::{Address, PinStrap, Scan};
+pub use breathe::Breathe;
+pub use countdown::Countdown;
+pub use editor::{DigitEditor, EditKey};
+#[cfg(feature = "eh-1")]
+pub use eh1::I2cEh1;
+pub use framebuffer::FrameBuffer;
+pub use interface::{Interface, SpiError, SpiInterface};
+pub use keypad::{KeyEvent, KeyEventKind, KeyEvents, KeyMap, KeyRepeat, Keypad};
+pub use multi::MultiMax6955;
+pub use pins::{PinError, PortPin, Ports};
+pub use sized::SizedMax6955;
+pub use split::{Display, Gpio, KeyScan, Parts};
+pub use state::DeviceState;
+pub use typestate::{Running, Shutdown, TypedMax6955};
+#[cfg(feature = "ufmt")]
+pub use writer::Writer;
 
 use bit_field::BitField;
-use hal::blocking::i2c::{Write, WriteRead};
+#[cfg(feature = "fixed")]
+use fixed::{types::extra::LeEqU32, FixedI32};
+use format::Field;
+use gpio::port_bit;
+use hal::blocking::delay::DelayMs;
 
 /// Default address
 pub const DEFAULT_SLAVE_ADDR: u8 = 0x60;
 
+/// Decimal point bit of a digit register
+pub const DECIMAL_POINT: u8 = 0x80;
+
+/// Highest intensity level
+pub const MAX_INTENSITY: u8 = 0x0F;
+
+/// Read-only blink phase bit of the configuration register
+const CONFIG_READ_ONLY: u8 = 0x80;
+
+/// Clear and blink timing reset bits of the configuration register, which act once
+const CONFIG_ONE_SHOT: u8 = 0x30;
+
+/// Configuration bits left out of the driver's copy
+const CONFIG_TRANSIENT: u8 = CONFIG_READ_ONLY | CONFIG_ONE_SHOT;
+
+/// Character used as the degree sign. The font has no dedicated glyph.
+pub const DEGREE_SIGN: u8 = b'*';
+
 /// MAX6955 driver
 pub struct Max6955<I2C> {
     i2c: I2C,
     addr: u8,
+    digit_map: DigitMap,
+    segment_map: SegmentMap,
+    digits: usize,
+    text: [u8; 8],
+    dots: u8,
+    cursor: usize,
+    wrap: Wrap,
+    overflow: Overflow,
+    padding: Padding,
+    separator: Separator,
+    intensity_mode: IntensityMode,
+    intensity: u8,
+    curve: BrightnessCurve,
+    calibration: Calibration,
+    blink_mask: u8,
+    key_masks: u32,
+    outputs: Option<u8>,
+    port_config: Option<u8>,
+    config: Option<u8>,
+    digit_type: u8,
+}
+
+impl<I2C> Max6955<I2C> {
+    /// Driver state after power-on, without touching the device
+    fn bare(i2c: I2C, addr: u8) -> Self {
+        Max6955 {
+            i2c,
+            addr,
+            digit_map: DigitMap::from(Order::Normal),
+            segment_map: SegmentMap::IDENTITY,
+            digits: 8,
+            text: [b' '; 8],
+            dots: 0,
+            cursor: 0,
+            wrap: Wrap::Wrap,
+            overflow: Overflow::Truncate,
+            padding: Padding::Blank,
+            separator: Separator::None,
+            intensity_mode: IntensityMode::Global,
+            intensity: 0,
+            curve: BrightnessCurve::Linear,
+            calibration: Calibration::NONE,
+            blink_mask: 0,
+            key_masks: 0,
+            outputs: None,
+            port_config: None,
+            config: None,
+            digit_type: 0,
+        }
+    }
 }
 
 impl<I2C, E> Max6955<I2C>
 where
-    I2C: WriteRead<Error = E> + Write<Error = E>,
+    I2C: Interface<Error = E>,
 {
     /// Construct a new MAX6955 driver instance with I2C peripheral and default address of `0x60`.
     ///
     /// # Arguments
     ///
-    /// * `i2c` - I2C interface
+    /// * `i2c` - I2C interface, or a `SpiInterface` for a MAX6954
     ///
     /// # Errors
     ///
     /// * `E` - returned in case there was an error reading/writing to the device
     ///
     pub fn new(i2c: I2C) -> Result<Self, E> {
-        let max6955 = Max6955 {
-            i2c,
-            addr: DEFAULT_SLAVE_ADDR,
-        };
-        Ok(max6955)
+        Self::with_address(i2c, Address::DEFAULT)
     }
 
     /// Construct a new MAX6955 driver instance with I2C peripheral and address.
@@ -54,32 +175,87 @@ where
     /// # Arguments
     ///
     /// * `i2c` - I2C interface
-    /// * `addr` - device address. This can be `0x60` ~ `0x6F`. See table 5 in the datasheet.
+    /// * `addr` - device address, checked by `Address::new`
     ///
     /// # Errors
     ///
     /// * `E` - returned in case there was an error reading/writing to the device
     ///
-    pub fn with_address(i2c: I2C, addr: u8) -> Result<Self, E> {
-        let max6955 = Max6955 { i2c, addr };
-        Ok(max6955)
+    pub fn with_address(i2c: I2C, addr: Address) -> Result<Self, E> {
+        Ok(Self::bare(i2c, addr.value()))
     }
 
     /// Set device address
     /// # Arguments
     ///
-    /// * `addr` - device address. This can be `0x60` ~ `0x6F`. See table 5 in the datasheet.
-    pub fn set_address(&mut self, addr: u8) {
-        self.addr = addr;
+    /// * `addr` - device address, checked by `Address::new`
+    pub fn set_address(&mut self, addr: Address) {
+        self.addr = addr.value();
+    }
+
+    /// Device address
+    pub fn address(&self) -> u8 {
+        self.addr
+    }
+
+    /// Check that a device responds at the address
+    ///
+    /// Reads the configuration register, which has no side effects.
+    /// Returns `false` on any bus error, e.g. no acknowledge.
+    pub fn probe(&mut self) -> bool {
+        self.read_register(Register::Configuration).is_ok()
+    }
+
+    /// Set Digit Order
+    /// # Arguments
+    ///
+    /// * `order` - `Order::Normal`: text starts at digit 0, `Order::Reversed`: text starts at digit 7
+    pub fn set_digit_order(&mut self, order: Order) {
+        self.digit_map = DigitMap::from(order);
+    }
+
+    /// Set Digit Map
+    /// # Arguments
+    ///
+    /// * `map` - `DigitMap` from text positions to digit registers, for boards with non-sequential digit routing
+    pub fn set_digit_map(&mut self, map: DigitMap) {
+        self.digit_map = map;
+    }
+
+    /// Set Segment Map
+    /// # Arguments
+    ///
+    /// * `map` - `SegmentMap` applied to raw segment data, for boards with out-of-order segment wiring
+    pub fn set_segment_map(&mut self, map: SegmentMap) {
+        self.segment_map = map;
+    }
+
+    /// Set Scan Limit
+    ///
+    /// The multiplex period is shared by the scanned digits only, so a board with fewer than
+    /// eight digits should scan just those to get full brightness.
+    /// # Arguments
+    ///
+    /// * `limit` - scanned digits, counted from digit 0. Text APIs only write the scanned digits.
+    pub fn set_scan_limit(&mut self, limit: ScanLimit) -> Result<(), E> {
+        self.write_register(Register::ScanLimit, limit.value())?;
+        self.digits = limit.digits();
+        Ok(())
     }
 
     /// Set Global Intensity
+    ///
+    /// Switches to `IntensityMode::Global` if needed.
     /// # Arguments
     ///
-    /// * `intensity` - intensity level `0`: lowest ~ `15`: highest
+    /// * `intensity` - intensity level `0`: lowest ~ `15`: highest. Higher values are clamped to `15`.
+    ///   The calibration is applied before writing.
     pub fn set_global_intensity(&mut self, intensity: u8) -> Result<(), E> {
-        self.write_register(Register::GlobalIntensity, intensity)?;
-        Ok(())
+        let intensity = intensity.min(MAX_INTENSITY);
+        let calibrated = self.calibration.apply(intensity);
+        self.write_register(Register::GlobalIntensity, calibrated)?;
+        self.intensity = intensity;
+        self.use_intensity_mode(IntensityMode::Global)
     }
 
     /// Control Blinking
@@ -88,8 +264,26 @@ where
     /// * `mode` - `BlinkMode::Enable`: blink, `BlinkMode::Disable`: not blink
     /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
     pub fn set_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
-        self.set_configuration_bit(ConfigBitFlag::Blink, mode.value())?;
-        self.set_configuration_bit(ConfigBitFlag::BlinkRate, rate.value())
+        let mut config = self.config()?;
+        config.set_bit(ConfigBitFlag::Blink.value(), mode.value());
+        config.set_bit(ConfigBitFlag::BlinkRate.value(), rate.value());
+        self.write_config(config)
+    }
+
+    /// Initialize the Display
+    ///
+    /// Clears both planes, sets decode mode, digit type, scan limit and global intensity,
+    /// then powers up, so nothing stale shows when the display turns on.
+    /// # Arguments
+    ///
+    /// * `config` - settings, e.g. `InitConfig::DEFAULT` with the fields needed changed
+    pub fn initialize(&mut self, config: InitConfig) -> Result<(), E> {
+        self.clear(ClearScope::Both)?;
+        self.set_decode_mode(config.decode_mode)?;
+        self.set_digit_type(config.digit_type)?;
+        self.set_scan_limit(config.scan_limit)?;
+        self.set_global_intensity(config.intensity)?;
+        self.powerup()
     }
 
     /// Power up Display
@@ -102,33 +296,85 @@ where
         self.set_configuration_bit(ConfigBitFlag::Shutdown, false)
     }
 
+    /// Read Configuration
+    ///
+    /// Returns the configuration register decoded into its fields.
+    pub fn configuration(&mut self) -> Result<Config, E> {
+        let config: u8 = self.read_register(Register::Configuration)?;
+        self.store_config(config);
+        Ok(Config::from(config))
+    }
+
+    /// Read whether the display is powered up
+    pub fn is_powered(&mut self) -> Result<bool, E> {
+        Ok(!self.configuration()?.shutdown)
+    }
+
+    /// Read whether blinking is enabled
+    pub fn is_blinking(&mut self) -> Result<bool, E> {
+        Ok(self.configuration()?.blink.value())
+    }
+
+    /// Read whether the display test is on
+    pub fn in_display_test(&mut self) -> Result<bool, E> {
+        Ok(self.read_register(Register::DisplayTest)?.get_bit(0))
+    }
+
+    /// Apply Configuration in one write
+    ///
+    /// Start from `Config::DEFAULT` or `configuration()` and change the fields needed.
+    /// `clear` and `sync` act on this write only and are not repeated by later writes.
+    /// # Arguments
+    ///
+    /// * `config` - configuration register contents
+    pub fn apply_config(&mut self, config: Config) -> Result<(), E> {
+        self.write_config(config.value())
+    }
+
+    /// Reload the driver's copies of device registers
+    ///
+    /// Configuration changes start from a copy of the configuration register, read once on
+    /// first use, and GPIO changes from copies of the port configuration and output levels.
+    /// Call this when someone else changed these registers.
+    pub fn resync(&mut self) -> Result<(), E> {
+        self.config = None;
+        self.config()?;
+        self.resync_gpio()
+    }
+
     /// Configure Digit Type
     /// # Arguments
     ///
-    /// * `digit_type` - one of four `DigitType`s
-    pub fn set_digit_type(&mut self, digit_type: DigitType) -> Result<(), E> {
-        self.write_register(Register::DigitType, digit_type.value())
+    /// * `digit_type` - one of four `DigitType`s, or a `DigitTypeConfig` for any mix of digit types
+    pub fn set_digit_type<T: Into<DigitTypeConfig>>(&mut self, digit_type: T) -> Result<(), E> {
+        let digit_type = digit_type.into().bits();
+        self.write_register(Register::DigitType, digit_type)?;
+        self.digit_type = digit_type;
+        Ok(())
     }
 
     /// Configure Pin Mode
+    ///
+    /// The rest of the port configuration is taken from the driver's copy, read once on first
+    /// use, see `resync_gpio`.
     /// # Arguments
     ///
-    /// * `port` - `0` ~ `4`
+    /// * `port` - `0` ~ `4`, see `read_pin`
     /// * `pin_mode`
     pub fn set_pin_mode(&mut self, port: usize, pin_mode: PinMode) -> Result<(), E> {
-        let mut port_config: u8 = self.read_register(Register::PortConfiguration)?;
-        let config = match pin_mode {
-            PinMode::Input => *port_config.set_bit(port, true),
-            PinMode::Output => *port_config.set_bit(port, false),
-        };
-        self.write_register(Register::PortConfiguration, config)
+        let bit = port_bit(port);
+        let mut port_config = self.port_config_bits()? & !bit;
+        if pin_mode == PinMode::Input {
+            port_config |= bit;
+        }
+        self.write_port_config(port_config)
     }
 
     /// Configure Decode Mode
     /// # Arguments
-    /// * `mode` - `DecodeMode`
-    pub fn set_decode_mode(&mut self, mode: DecodeMode) -> Result<(), E> {
-        self.write_register(Register::DecodeMode, mode.value())
+    /// * `mode` - `DecodeMode`, or a `DecodeModeConfig` for any mix of decoded digit pairs
+    pub fn set_decode_mode<T: Into<DecodeModeConfig>>(&mut self, mode: T) -> Result<(), E> {
+        self.write_register(Register::DecodeMode, mode.into().bits())
     }
 
     /// Display Test function
@@ -142,188 +388,1710 @@ where
         }
     }
 
+    /// Timed Display Test
+    ///
+    /// Lights all segments for `duration_ms`, then turns the test off again. The digit
+    /// registers are not touched, so the previous display comes back as it was.
+    /// # Arguments
+    /// * `duration_ms` - test time in milliseconds
+    /// * `delay` - delay provider
+    pub fn test_for<D: DelayMs<u32>>(&mut self, duration_ms: u32, delay: &mut D) -> Result<(), E> {
+        self.test(true)?;
+        delay.delay_ms(duration_ms);
+        self.test(false)
+    }
+
+    /// Communication Self Test
+    ///
+    /// Writes two bit patterns to the plane P1 register of digit 7 and reads each back, then
+    /// puts the original value back. Plane P1 is only shown during the off phase of blinking.
+    pub fn self_test(&mut self) -> Result<SelfTest, E> {
+        let reg = Register::Digit7Plane1;
+        let original = self.read_register(reg)?;
+        let mut result = SelfTest::Pass;
+        for &written in [0x55, 0xAA].iter() {
+            self.write_register(reg, written)?;
+            let read = self.read_register(reg)?;
+            if read != written {
+                result = SelfTest::Mismatch { written, read };
+                break;
+            }
+        }
+        self.write_register(reg, original)?;
+        Ok(result)
+    }
+
     /// Clear Display
+    /// The cursor returns to the first position.
     pub fn clear_display(&mut self) -> Result<(), E> {
+        self.cursor = 0;
         self.write_str("")
     }
 
-    /// Write Text
+    /// Clear Digit Data
+    ///
+    /// `ClearScope::Both` uses the clear bit of the configuration register, which is faster than
+    /// writing blanks. A single plane is cleared by writing blanks to it.
     /// # Arguments
-    /// * `text` - text to write
-    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
-        let mut row: [u8; 9] = [b' '; 9];
-        row[0] = Register::Digit0Plane0.addr();
-        for (i, c) in text.chars().enumerate() {
-            row[i + 1] = match c {
-                ' '..='~' => c as u8,
-                _ => b' ',
+    /// * `scope` - `ClearScope::Plane0`, `ClearScope::Plane1` or `ClearScope::Both`
+    pub fn clear(&mut self, scope: ClearScope) -> Result<(), E> {
+        match scope {
+            ClearScope::Plane0 => self.write_plane(Plane::P0, &[b' '; 8])?,
+            ClearScope::Plane1 => return self.write_plane(Plane::P1, &[b' '; 8]),
+            ClearScope::Both => {
+                let config = self.config()? | 1 << ConfigBitFlag::ClearDigit.value();
+                self.write_register(Register::Configuration, config)?;
             }
         }
-        self.i2c.write(self.addr, &row)
+        self.text = [b' '; 8];
+        self.dots = 0;
+        self.cursor = 0;
+        Ok(())
     }
 
-    fn write_register(&mut self, reg: Register, byte: u8) -> Result<(), E> {
-        self.i2c.write(self.addr, &[reg.addr(), byte])
+    /// Clear one Digit Plane
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    pub fn clear_plane(&mut self, plane: Plane) -> Result<(), E> {
+        match plane {
+            Plane::P0 => self.clear(ClearScope::Plane0),
+            Plane::P1 => self.clear(ClearScope::Plane1),
+        }
     }
 
-    fn set_configuration_bit(&mut self, bit: ConfigBitFlag, set: bool) -> Result<(), E> {
-        let mut config: u8 = self.read_register(Register::Configuration)?;
-        config.set_bit(bit.value(), set);
-        self.write_register(Register::Configuration, config)
+    /// Set Cursor Position for `write_char`
+    /// # Arguments
+    /// * `position` - text position, limited to the number of scanned digits
+    pub fn set_cursor(&mut self, position: usize) {
+        self.cursor = position.min(self.digits);
     }
 
-    fn read_register(&mut self, reg: Register) -> Result<u8, E> {
-        let mut buffer: [u8; 8] = [0; 8];
-        self.read_registers(reg, &mut buffer)?;
-        Ok(buffer[0])
+    /// Current cursor position
+    pub fn cursor(&self) -> usize {
+        self.cursor
     }
 
-    fn read_registers(&mut self, reg: Register, buffer: &mut [u8; 8]) -> Result<(), E> {
-        self.i2c.write_read(self.addr, &[reg.addr()], buffer)
+    /// Set what `write_char` does once the cursor has passed the last scanned digit
+    /// # Arguments
+    /// * `wrap` - `Wrap::Wrap`, `Wrap::Scroll` or `Wrap::Stop`
+    pub fn set_wrap(&mut self, wrap: Wrap) {
+        self.wrap = wrap;
     }
-}
 
-/// Register address. see Table 7
-pub enum Register {
-    NoOp = 0x00,
-    DecodeMode = 0x01,
-    GlobalIntensity = 0x02,
-    ScanLimit = 0x03,
-    Configuration = 0x04,
-    GpioData = 0x05,
-    PortConfiguration = 0x06,
-    DisplayTest = 0x07,
-    KeyAMaskDebounce = 0x08,
-    KeyBMaskDebounce = 0x09,
-    KeyCMaskDebounce = 0x0A,
-    KeyDMaskDebounce = 0x0B,
-    DigitType = 0x0C,
-    KeyBPressed = 0x0D,
-    KeyCPressed = 0x0E,
-    KeyDPressed = 0x0F,
-    Intensity10 = 0x10,
-    Intensity32 = 0x11,
-    Intensity54 = 0x12,
-    Intensity76 = 0x13,
-    Intensity10a = 0x14,
-    Intensity32a = 0x15,
-    Intensity54a = 0x16,
-    Intensity76a = 0x17,
-    Digit0Plane0 = 0x20,
-    Digit1Plane0 = 0x21,
-    Digit2Plane0 = 0x22,
-    Digit3Plane0 = 0x23,
-    Digit4Plane0 = 0x24,
-    Digit5Plane0 = 0x25,
-    Digit6Plane0 = 0x26,
-    Digit7Plane0 = 0x27,
-    Digit0Plane1 = 0x40,
-    Digit1Plane1 = 0x41,
-    Digit2Plane1 = 0x42,
-    Digit3Plane1 = 0x43,
-    Digit4Plane1 = 0x44,
-    Digit5Plane1 = 0x45,
-    Digit6Plane1 = 0x46,
-    Digit7Plane1 = 0x47,
-}
+    /// Write a character at the cursor and advance the cursor
+    /// # Arguments
+    /// * `c` - character to write
+    pub fn write_char(&mut self, c: char) -> Result<(), E> {
+        if self.cursor >= self.digits {
+            match self.wrap {
+                Wrap::Wrap => self.cursor = 0,
+                Wrap::Scroll => return self.shift_left(Some(c)),
+                Wrap::Stop => return Ok(()),
+            }
+        }
+        let position = self.cursor;
+        self.write_digit(position, ascii(c))?;
+        self.cursor += 1;
+        Ok(())
+    }
 
-impl Register {
-    /// return register address as u8
-    pub fn addr(self) -> u8 {
-        self as u8
+    /// Write Text
+    /// # Arguments
+    /// * `text` - text to write. Characters beyond the scan limit are dropped.
+    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
+        self.write_iter(text.chars())
     }
-}
 
-/// Configuration Register bits. see Table 17
-pub enum ConfigBitFlag {
-    Shutdown = 0x00,
-    BlinkRate = 0x02,
-    Blink = 0x03,
-    BlinkTiming = 0x04,
-    ClearDigit = 0x05,
-    Intensity = 0x06,
-    BlinkPhase = 0x07,
-}
+    /// Write Characters
+    /// # Arguments
+    /// * `chars` - characters to write. At most one more than there are scanned digits is consumed.
+    pub fn write_iter(&mut self, chars: impl Iterator<Item = char>) -> Result<(), E> {
+        self.write_bytes(chars.map(ascii))
+    }
 
-impl ConfigBitFlag {
-    /// return enum value as usize
-    pub fn value(self) -> usize {
-        self as usize
+    /// Write ASCII Text
+    /// # Arguments
+    /// * `bytes` - ASCII text to write. Bytes outside the printable range are shown as blanks.
+    pub fn write_ascii(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.write_bytes(bytes.iter().map(|&byte| ascii(byte as char)))
     }
-}
 
-/// Display Digit Configuration. see Table 14
-pub enum DigitType {
-    /// Digits 7 to 0 are 16-segment or 7- segment digits.
-    Seg7_16 = 0x00,
-    /// Digit 0 is a 14-segment digit, digits 7 to 1 are 16-segment or 7- segment digits.
-    D0_14 = 0x01,
-    /// Digits 2 to 0 are 14-segment digits, digits 7 to 3 are 16- segment or 7-segment digits.
-    D0D2_14 = 0x07,
-    /// Digits 7 to 0 are 14-segment digits.
-    Seg14 = 0xFF,
-}
+    /// Set what text writes show when the text is longer than the scanned digits
+    /// # Arguments
+    /// * `overflow` - `Overflow::Truncate` or `Overflow::Fill`
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
 
-impl DigitType {
-    /// return enum value as u8
-    pub fn value(self) -> u8 {
-        self as u8
+    /// Set how the numeric writes pad numbers
+    /// # Arguments
+    /// * `padding` - `Padding::Blank` or `Padding::Zeros` with the minimum number of digits
+    pub fn set_padding(&mut self, padding: Padding) {
+        self.padding = padding;
     }
-}
 
-/// Decode Mode. see Table 15
-pub enum DecodeMode {
-    /// No decode for digit pairs 7 to 0.
-    NoDecode = 0x00,
-    /// Hexadecimal decode for digit pair 0, no decode for digit pairs 7 to 1.
-    HexD0 = 0x01,
-    /// Hexadecimal decode for digit pairs 2 to 0, no decode for digit pairs 7 to 3.
-    HexD0D2 = 0x07,
-    /// Hexadecimal decode for digit pairs 7 to 0.
-    Hex = 0xFF,
-}
+    /// Set the thousands separator of decimal numeric writes
+    /// # Arguments
+    /// * `separator` - `Separator::None`, `Separator::Dot` or `Separator::Apostrophe`
+    pub fn set_separator(&mut self, separator: Separator) {
+        self.separator = separator;
+    }
 
-impl DecodeMode {
-    /// return enum value as u8
-    pub fn value(self) -> u8 {
-        self as u8
+    /// Write Unsigned Integer
+    /// # Arguments
+    /// * `value` - value to write right-aligned with leading blanks. If it does not fit,
+    ///   the lowest digits are shown unless an overflow indicator is set.
+    pub fn write_u32(&mut self, value: u32) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_u32(value);
+        self.write_field(field)
     }
-}
 
-/// Pin Mode Input/Output
-pub enum PinMode {
-    Input,
-    Output,
-}
+    /// Write Signed Integer
+    /// # Arguments
+    /// * `value` - value to write right-aligned, with a `-` directly left of the highest digit.
+    ///   If it does not fit, the `-` takes the leftmost digit and the lowest digits fill the
+    ///   rest, unless an overflow indicator is set.
+    pub fn write_i32(&mut self, value: i32) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_i32(value);
+        self.write_field(field)
+    }
 
-/// Blink Mode Enable/Disable
-pub enum BlinkMode {
-    Disable,
-    Enable,
-}
+    /// Write Floating Point Number
+    /// # Arguments
+    /// * `value` - value to write right-aligned, rounded to `decimals` places. If it does not
+    ///   fit, the lowest digits are shown unless an overflow indicator is set. NaN and infinity
+    ///   show the overflow indicator, or nothing with `Overflow::Truncate`.
+    /// * `decimals` - number of digits after the decimal point, which is lit on the digit before them
+    pub fn write_f32(&mut self, value: f32, decimals: usize) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_f32(value, decimals);
+        self.write_field(field)
+    }
 
-impl BlinkMode {
-    /// return enum value as bool
-    pub fn value(self) -> bool {
-        match self {
-            BlinkMode::Disable => false,
-            BlinkMode::Enable => true,
+    /// Write Fixed-Point Number
+    /// # Arguments
+    /// * `value` - value to write right-aligned, rounded to `decimals` places
+    /// * `decimals` - number of digits after the decimal point, which is lit on the digit before them
+    #[cfg(feature = "fixed")]
+    pub fn write_fixed<Frac: LeEqU32>(
+        &mut self,
+        value: FixedI32<Frac>,
+        decimals: usize,
+    ) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_fixed_point(value.to_bits(), FixedI32::<Frac>::FRAC_NBITS, decimals);
+        self.write_field(field)
+    }
+
+    /// Write Temperature
+    /// # Arguments
+    /// * `value_c_x10` - temperature in tenths of a degree Celsius
+    /// * `unit` - `Unit::Celsius` or `Unit::Fahrenheit`, e.g. `"23.5*C"` or `"-4.0*F"`
+    pub fn write_temperature(&mut self, value_c_x10: i16, unit: Unit) -> Result<(), E> {
+        let value = match unit {
+            Unit::Celsius => value_c_x10 as i32,
+            Unit::Fahrenheit => {
+                let scaled = value_c_x10 as i32 * 9;
+                (scaled + if scaled < 0 { -2 } else { 2 }) / 5 + 320
+            }
+        };
+        let mut field = self.field();
+        field.push(unit.symbol());
+        field.push(DEGREE_SIGN);
+        field.push_fixed_i32(value, 1);
+        self.write_field(field)
+    }
+
+    /// Write Time
+    /// # Arguments
+    /// * `hours`, `minutes`, `seconds` - time to write right-aligned, two digits each
+    /// * `format` - `TimeFormat::Hmm`: `"12.34"`, `TimeFormat::Hms`: `"12.34.56"`.
+    ///   The decimal points serve as the colon.
+    /// * `blink_colon` - `true`: flash the colon using plane P1 and slow blinking.
+    ///   Blinking digits keep blinking either way.
+    pub fn write_time(
+        &mut self,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        format: TimeFormat,
+        blink_colon: bool,
+    ) -> Result<(), E> {
+        let field = self.time_field(hours, minutes, seconds, format, true);
+        self.write_field(field)?;
+        if blink_colon {
+            let field = self.time_field(hours, minutes, seconds, format, false);
+            let mut digits = self.resolve(field);
+            self.blank_blinking(&mut digits);
+            self.write_plane(Plane::P1, &digits)?;
+            self.set_blink(BlinkMode::Enable, BlinkRate::Slow)?;
+        } else if self.blink_mask == 0 {
+            self.write_blink_plane()?;
         }
+        Ok(())
     }
-}
 
-/// Blink Rate Fast/Slow
-pub enum BlinkRate {
-    Fast,
-    Slow,
-}
+    /// Write Date
+    /// # Arguments
+    /// * `day`, `month`, `year` - date to write right-aligned. Only the last two digits of the year are shown.
+    /// * `format` - `DateFormat::DdMmYy`: `"31.12.24"`, `DateFormat::MmDdYy`: `"12-31-24"`,
+    ///   `DateFormat::YyMmDd`: `"24-12-31"`
+    pub fn write_date(
+        &mut self,
+        day: u8,
+        month: u8,
+        year: u16,
+        format: DateFormat,
+    ) -> Result<(), E> {
+        let year = (year % 100) as u8;
+        let (pairs, dots) = match format {
+            DateFormat::DdMmYy => ([day, month, year], true),
+            DateFormat::MmDdYy => ([month, day, year], false),
+            DateFormat::YyMmDd => ([year, month, day], false),
+        };
+        let mut field = self.field();
+        field.push_pair(pairs[2], false);
+        for &pair in pairs[..2].iter().rev() {
+            if !dots {
+                field.push(b'-');
+            }
+            field.push_pair(pair, dots);
+        }
+        self.write_field(field)
+    }
 
-impl BlinkRate {
-    /// return enum value as bool
-    pub fn value(self) -> bool {
-        match self {
-            BlinkRate::Slow => false,
-            BlinkRate::Fast => true,
+    /// Write Value with SI Prefix
+    /// # Arguments
+    /// * `value` - value in base units, shown scaled to `1` ~ `999` with a prefix from
+    ///   `p` to `T`, e.g. `"1.234k"` or `"12.34M"`. As many decimals as fit are shown, up to three.
+    /// * `unit` - unit written after the prefix, e.g. `"Hz"`, or `""`
+    pub fn write_engineering(&mut self, value: f32, unit: &str) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_engineering(value, unit);
+        self.write_field(field)
+    }
+
+    /// Write Hexadecimal Number
+    /// # Arguments
+    /// * `value` - value to write right-aligned
+    /// * `prefix` - `true`: put `0x` in front if there is room
+    pub fn write_hex_u32(&mut self, value: u32, prefix: bool) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_radix(value, 16);
+        if prefix {
+            field.push_prefix(b"0x");
+        }
+        self.write_field(field)
+    }
+
+    /// Write Binary Number
+    /// # Arguments
+    /// * `value` - value to write right-aligned
+    /// * `prefix` - `true`: put `b` in front if there is room
+    pub fn write_bin_u8(&mut self, value: u8, prefix: bool) -> Result<(), E> {
+        let mut field = self.field();
+        field.push_radix(value as u32, 2);
+        if prefix {
+            field.push_prefix(b"b");
         }
+        self.write_field(field)
+    }
+
+    /// Write Hex-Decoded Digits
+    ///
+    /// The codes go to both planes as they are, and the driver's text copy is left alone, so
+    /// `current_text` and the text editing methods do not see them. A blank in plane P1 would
+    /// decode to `0`, so per-digit blinking is turned off.
+    /// # Arguments
+    /// * `codes` - digit values `0x0` ~ `0xF` in text order, for 7-segment digits in hexadecimal
+    ///   decode mode. Set `DECIMAL_POINT` to light the decimal point. Missing digits show `0`.
+    pub fn write_decoded_digits(&mut self, codes: &[u8]) -> Result<(), E> {
+        let mut digits: [u8; 8] = [0; 8];
+        for (digit, &code) in digits.iter_mut().zip(codes.iter()) {
+            *digit = code & (DECIMAL_POINT | 0x0F);
+        }
+        self.blink_mask = 0;
+        self.write_plane(Plane::P0, &digits)?;
+        self.write_plane(Plane::P1, &digits)
+    }
+
+    /// Write Unsigned Integer in Hex-Decode Mode
+    ///
+    /// Writes the value as hexadecimal digits, see `write_decoded_digits`.
+    /// # Arguments
+    /// * `value` - value to write with leading zeros, for 7-segment digits in hexadecimal decode mode.
+    ///   The chip has no blank code, and only the lowest nibbles of a value that does not fit are shown.
+    pub fn write_decoded_u32(&mut self, mut value: u32) -> Result<(), E> {
+        let mut digits: [u8; 8] = [0; 8];
+        for digit in digits[..self.digits].iter_mut().rev() {
+            *digit = (value & 0x0F) as u8;
+            value >>= 4;
+        }
+        self.write_decoded_digits(&digits)
+    }
+
+    /// Write Raw Segment Data
+    /// # Arguments
+    /// * `segments` - segment bits per digit in text order, for 7-segment digits in no-decode mode.
+    ///   The segment map is applied before writing. Missing digits are blanked.
+    pub fn write_segments(&mut self, segments: &[u8]) -> Result<(), E> {
+        let mut digits: [u8; 8] = [0; 8];
+        for (digit, &data) in digits.iter_mut().zip(segments.iter()) {
+            *digit = self.segment_map.apply(data);
+        }
+        self.write_digits(&digits)
+    }
+
+    /// Shift the displayed text one position to the left
+    /// # Arguments
+    /// * `fill` - character fed in at the right end, blank if `None`
+    pub fn shift_left(&mut self, fill: Option<char>) -> Result<(), E> {
+        let mut digits = self.shadow();
+        digits[..self.digits].rotate_left(1);
+        digits[self.digits - 1] = fill.map_or(b' ', ascii);
+        self.write_digits(&digits)
+    }
+
+    /// Append a character at the right end, scrolling the displayed text left
+    /// # Arguments
+    /// * `c` - character to append
+    pub fn push_char(&mut self, c: char) -> Result<(), E> {
+        self.shift_left(Some(c))
+    }
+
+    /// Shift the displayed text one position to the right
+    /// # Arguments
+    /// * `fill` - character fed in at the left end, blank if `None`
+    pub fn shift_right(&mut self, fill: Option<char>) -> Result<(), E> {
+        let mut digits = self.shadow();
+        digits[..self.digits].rotate_right(1);
+        digits[0] = fill.map_or(b' ', ascii);
+        self.write_digits(&digits)
+    }
+
+    /// Text last written to plane P0, one character per scanned digit, without decimal points
+    ///
+    /// Copy it before showing a transient message and write it back with `write_ascii` to restore
+    /// the screen.
+    pub fn current_text(&self) -> &str {
+        core::str::from_utf8(&self.text[..self.digits]).unwrap_or("")
+    }
+
+    /// Show a Message Briefly
+    ///
+    /// Shows `text`, waits `duration_ms` and writes the previous text, decimal points and cursor
+    /// back, e.g. for a `"SAVED"` notice.
+    /// # Arguments
+    /// * `text` - message to show
+    /// * `duration_ms` - time to show it in milliseconds
+    /// * `delay` - delay provider
+    pub fn flash_message<D: DelayMs<u32>>(
+        &mut self,
+        text: &str,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), E> {
+        let saved = self.shadow();
+        let cursor = self.cursor;
+        self.write_str(text)?;
+        delay.delay_ms(duration_ms);
+        self.write_digits(&saved)?;
+        self.cursor = cursor;
+        Ok(())
+    }
+
+    /// Read Display
+    /// # Arguments
+    /// * `plane` - `Plane::P0` or `Plane::P1`
+    ///
+    /// Returns the eight digit registers of the plane in text order, see `set_digit_map`.
+    /// Digits beyond the scan limit follow the scanned ones.
+    pub fn read_display(&mut self, plane: Plane) -> Result<[u8; 8], E> {
+        let mut registers: [u8; 8] = [0; 8];
+        self.read_registers(plane.digit0(), &mut registers)?;
+        let map = self.digit_map.limit(self.digits);
+        let mut digits: [u8; 8] = [0; 8];
+        for (i, digit) in digits.iter_mut().enumerate() {
+            *digit = registers[map.digit(i)];
+        }
+        Ok(digits)
+    }
+
+    fn write_digits(&mut self, digits: &[u8; 8]) -> Result<(), E> {
+        self.write_plane(Plane::P0, digits)?;
+        for (i, &digit) in digits.iter().enumerate() {
+            self.store(i, digit);
+        }
+        if self.blink_mask != 0 {
+            self.write_blink_plane()?;
+        }
+        Ok(())
+    }
+
+    fn write_plane(&mut self, plane: Plane, digits: &[u8; 8]) -> Result<(), E> {
+        let map = self.digit_map.limit(self.digits);
+        let mut registers: [u8; 8] = [0; 8];
+        for (i, digit) in digits.iter().take(self.digits).enumerate() {
+            registers[map.digit(i)] = *digit;
+        }
+        self.write_registers(plane.digit0(), &registers[..self.digits])
+    }
+
+    fn write_bytes(&mut self, mut bytes: impl Iterator<Item = u8>) -> Result<(), E> {
+        let mut digits: [u8; 8] = [b' '; 8];
+        for (digit, byte) in digits.iter_mut().take(self.digits).zip(&mut bytes) {
+            *digit = byte;
+        }
+        if bytes.next().is_some() {
+            self.overflow.apply(&mut digits);
+        }
+        self.write_digits(&digits)
+    }
+
+    fn field(&self) -> Field {
+        Field::new(self.digits, self.padding, self.separator)
+    }
+
+    fn write_field(&mut self, field: Field) -> Result<(), E> {
+        let digits = self.resolve(field);
+        self.write_digits(&digits)
+    }
+
+    fn resolve(&self, field: Field) -> [u8; 8] {
+        let (mut digits, overflow) = field.finish();
+        if overflow {
+            self.overflow.apply(&mut digits);
+        }
+        digits
+    }
+
+    fn time_field(
+        &self,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        format: TimeFormat,
+        colon: bool,
+    ) -> Field {
+        let mut field = self.field();
+        if let TimeFormat::Hms = format {
+            field.push_pair(seconds, false);
+            field.push_pair(minutes, colon);
+        } else {
+            field.push_pair(minutes, false);
+        }
+        field.push_pair(hours, colon);
+        field
+    }
+
+    fn physical_digit(&self, position: usize) -> usize {
+        self.digit_map.limit(self.digits).digit(position)
+    }
+
+    fn write_digit(&mut self, position: usize, byte: u8) -> Result<(), E> {
+        let digit = self.physical_digit(position) as u8;
+        self.i2c
+            .write_registers(self.addr, Register::Digit0Plane0.addr() + digit, &[byte])?;
+        self.store(position, byte);
+        if self.blink_mask != 0 {
+            let byte = if self.blink_mask.get_bit(position) {
+                b' '
+            } else {
+                byte
+            };
+            self.i2c
+                .write_registers(self.addr, Register::Digit0Plane1.addr() + digit, &[byte])?;
+        }
+        Ok(())
+    }
+
+    fn store(&mut self, position: usize, byte: u8) {
+        self.text[position] = byte & !DECIMAL_POINT;
+        self.dots.set_bit(position, byte & DECIMAL_POINT != 0);
+    }
+
+    fn shadow(&self) -> [u8; 8] {
+        let mut digits = self.text;
+        for (i, digit) in digits.iter_mut().enumerate() {
+            if self.dots.get_bit(i) {
+                *digit |= DECIMAL_POINT;
+            }
+        }
+        digits
+    }
+
+    /// Write a Register
+    ///
+    /// Raw access for features without a dedicated method. The driver's copies of the
+    /// configuration, port configuration, output levels and display text are not updated,
+    /// see `resync`.
+    /// # Arguments
+    ///
+    /// * `reg` - register
+    /// * `byte` - value
+    pub fn write_register(&mut self, reg: Register, byte: u8) -> Result<(), E> {
+        self.write_registers(reg, &[byte])
+    }
+
+    fn set_configuration_bit(&mut self, bit: ConfigBitFlag, set: bool) -> Result<(), E> {
+        let mut config = self.config()?;
+        config.set_bit(bit.value(), set);
+        self.write_config(config)
+    }
+
+    /// Configuration register value from the driver's copy, read from the device the first time
+    fn config(&mut self) -> Result<u8, E> {
+        match self.config {
+            Some(config) => Ok(config),
+            None => {
+                let config = self.read_register(Register::Configuration)?;
+                Ok(self.store_config(config))
+            }
+        }
+    }
+
+    /// Write the configuration register and keep a copy
+    fn write_config(&mut self, config: u8) -> Result<(), E> {
+        self.write_register(Register::Configuration, config)?;
+        self.store_config(config);
+        Ok(())
+    }
+
+    /// Keep a copy of the configuration register without the bits that do not persist
+    fn store_config(&mut self, config: u8) -> u8 {
+        let config = config & !CONFIG_TRANSIENT;
+        self.config = Some(config);
+        self.intensity_mode = IntensityMode::from(config.get_bit(ConfigBitFlag::Intensity.value()));
+        config
+    }
+
+    /// Write Consecutive Registers
+    ///
+    /// Writes up to eight registers from `reg` on in one transaction, using the address
+    /// auto-increment. Values beyond the eighth are dropped. The driver's copies of device
+    /// registers are not updated, see `resync`.
+    /// # Arguments
+    ///
+    /// * `reg` - first register
+    /// * `bytes` - register values
+    pub fn write_registers(&mut self, reg: Register, bytes: &[u8]) -> Result<(), E> {
+        self.i2c.write_registers(self.addr, reg.addr(), bytes)
+    }
+
+    /// Read a Register
+    ///
+    /// Raw access for features without a dedicated method. Some addresses read back something
+    /// else than what was written, e.g. the key mask registers return the debounced keys.
+    /// # Arguments
+    ///
+    /// * `reg` - register
+    pub fn read_register(&mut self, reg: Register) -> Result<u8, E> {
+        let mut buffer: [u8; 1] = [0];
+        self.read_registers(reg, &mut buffer)?;
+        Ok(buffer[0])
+    }
+
+    /// Read Consecutive Registers
+    ///
+    /// Reads `buffer.len()` registers from `reg` on in one transaction, using the address
+    /// auto-increment. Mind that reading the key debounce registers clears the key interrupt.
+    /// # Arguments
+    ///
+    /// * `reg` - first register
+    /// * `buffer` - register values
+    pub fn read_registers(&mut self, reg: Register, buffer: &mut [u8]) -> Result<(), E> {
+        self.i2c.read_registers(self.addr, reg.addr(), buffer)
+    }
+}
+
+/// Map a char onto the printable ASCII range of the font. Anything else becomes a blank.
+fn ascii(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        _ => b' ',
+    }
+}
+
+/// Check that `map` contains each of `0` ~ `7` exactly once.
+fn is_permutation(map: &[u8; 8]) -> bool {
+    let mut seen: u8 = 0;
+    for &index in map.iter() {
+        if index > 7 || seen.get_bit(index as usize) {
+            return false;
+        }
+        seen.set_bit(index as usize, true);
+    }
+    true
+}
+
+/// Errors of argument checks. Bus errors are returned as the I2C error type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The address is outside `0x60` ~ `0x6F`.
+    InvalidAddress,
+}
+
+/// Result of `self_test`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelfTest {
+    /// All patterns read back as written.
+    Pass,
+    /// A pattern read back differently.
+    Mismatch { written: u8, read: u8 },
+}
+
+/// Register address. see Table 7
+#[derive(Clone, Copy)]
+pub enum Register {
+    NoOp = 0x00,
+    DecodeMode = 0x01,
+    GlobalIntensity = 0x02,
+    ScanLimit = 0x03,
+    Configuration = 0x04,
+    GpioData = 0x05,
+    PortConfiguration = 0x06,
+    DisplayTest = 0x07,
+    KeyAMaskDebounce = 0x08,
+    KeyBMaskDebounce = 0x09,
+    KeyCMaskDebounce = 0x0A,
+    KeyDMaskDebounce = 0x0B,
+    DigitType = 0x0C,
+    KeyBPressed = 0x0D,
+    KeyCPressed = 0x0E,
+    KeyDPressed = 0x0F,
+    Intensity10 = 0x10,
+    Intensity32 = 0x11,
+    Intensity54 = 0x12,
+    Intensity76 = 0x13,
+    Intensity10a = 0x14,
+    Intensity32a = 0x15,
+    Intensity54a = 0x16,
+    Intensity76a = 0x17,
+    Digit0Plane0 = 0x20,
+    Digit1Plane0 = 0x21,
+    Digit2Plane0 = 0x22,
+    Digit3Plane0 = 0x23,
+    Digit4Plane0 = 0x24,
+    Digit5Plane0 = 0x25,
+    Digit6Plane0 = 0x26,
+    Digit7Plane0 = 0x27,
+    Digit0Plane1 = 0x40,
+    Digit1Plane1 = 0x41,
+    Digit2Plane1 = 0x42,
+    Digit3Plane1 = 0x43,
+    Digit4Plane1 = 0x44,
+    Digit5Plane1 = 0x45,
+    Digit6Plane1 = 0x46,
+    Digit7Plane1 = 0x47,
+}
+
+impl Register {
+    /// Key A pressed register. It shares its address with the digit type register, which is write-only.
+    pub const KEY_A_PRESSED: Register = Register::DigitType;
+
+    /// return register address as u8
+    pub fn addr(self) -> u8 {
+        self as u8
+    }
+
+    /// return the intensity register of `bank` holding digit `digit` (`0` ~ `7`)
+    pub fn intensity(bank: IntensityBank, digit: usize) -> Register {
+        match (bank, digit / 2) {
+            (IntensityBank::Primary, 0) => Register::Intensity10,
+            (IntensityBank::Primary, 1) => Register::Intensity32,
+            (IntensityBank::Primary, 2) => Register::Intensity54,
+            (IntensityBank::Primary, _) => Register::Intensity76,
+            (IntensityBank::Secondary, 0) => Register::Intensity10a,
+            (IntensityBank::Secondary, 1) => Register::Intensity32a,
+            (IntensityBank::Secondary, 2) => Register::Intensity54a,
+            (IntensityBank::Secondary, _) => Register::Intensity76a,
+        }
+    }
+}
+
+/// Configuration Register bits. see Table 17
+pub enum ConfigBitFlag {
+    Shutdown = 0x00,
+    BlinkRate = 0x02,
+    Blink = 0x03,
+    BlinkTiming = 0x04,
+    ClearDigit = 0x05,
+    Intensity = 0x06,
+    BlinkPhase = 0x07,
+}
+
+impl ConfigBitFlag {
+    /// return enum value as usize
+    pub fn value(self) -> usize {
+        self as usize
+    }
+}
+
+/// Display Digit Configuration. see Table 14
+#[derive(Clone, Copy)]
+pub enum DigitType {
+    /// Digits 7 to 0 are 16-segment or 7- segment digits.
+    Seg7_16 = 0x00,
+    /// Digit 0 is a 14-segment digit, digits 7 to 1 are 16-segment or 7- segment digits.
+    D0_14 = 0x01,
+    /// Digits 2 to 0 are 14-segment digits, digits 7 to 3 are 16- segment or 7-segment digits.
+    D0D2_14 = 0x07,
+    /// Digits 7 to 0 are 14-segment digits.
+    Seg14 = 0xFF,
+}
+
+impl DigitType {
+    /// return enum value as u8
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Display Digit Configuration of each digit, bit `n` set: digit `n` is a 14-segment digit
+///
+/// Digits are register digits `0` ~ `7`, not text positions.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DigitTypeConfig(u8);
+
+impl DigitTypeConfig {
+    /// All digits are 16-segment or 7-segment digits.
+    pub const SEG7_16: DigitTypeConfig = DigitTypeConfig(0x00);
+    /// All digits are 14-segment digits.
+    pub const SEG14: DigitTypeConfig = DigitTypeConfig(0xFF);
+
+    /// return the configuration of register value `bits`
+    pub const fn from_bits(bits: u8) -> Self {
+        DigitTypeConfig(bits)
+    }
+
+    /// return the register value
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// return the configuration with digit `digit` (`0` ~ `7`) set to 14-segment if `seg14`,
+    /// otherwise to 16-segment or 7-segment
+    pub const fn with_digit(self, digit: u8, seg14: bool) -> Self {
+        let bit = 1 << (digit & 0x07);
+        if seg14 {
+            DigitTypeConfig(self.0 | bit)
+        } else {
+            DigitTypeConfig(self.0 & !bit)
+        }
+    }
+
+    /// return `true` if digit `digit` (`0` ~ `7`) is a 14-segment digit
+    pub fn is_seg14(self, digit: u8) -> bool {
+        self.0.get_bit((digit & 0x07) as usize)
+    }
+}
+
+impl From<DigitType> for DigitTypeConfig {
+    fn from(digit_type: DigitType) -> Self {
+        DigitTypeConfig(digit_type.value())
+    }
+}
+
+/// Decode Mode. see Table 15
+#[derive(Clone, Copy)]
+pub enum DecodeMode {
+    /// No decode for digit pairs 7 to 0.
+    NoDecode = 0x00,
+    /// Hexadecimal decode for digit pair 0, no decode for digit pairs 7 to 1.
+    HexD0 = 0x01,
+    /// Hexadecimal decode for digit pairs 2 to 0, no decode for digit pairs 7 to 3.
+    HexD0D2 = 0x07,
+    /// Hexadecimal decode for digit pairs 7 to 0.
+    Hex = 0xFF,
+}
+
+impl DecodeMode {
+    /// return enum value as u8
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Decode Mode of each digit pair, bit `n` set: hexadecimal decode for digit pair `n`
+#[derive(Clone, Copy, PartialEq)]
+pub struct DecodeModeConfig(u8);
+
+impl DecodeModeConfig {
+    /// No decode for digit pairs 7 to 0.
+    pub const NO_DECODE: DecodeModeConfig = DecodeModeConfig(0x00);
+    /// Hexadecimal decode for digit pairs 7 to 0.
+    pub const HEX: DecodeModeConfig = DecodeModeConfig(0xFF);
+
+    /// return the configuration of register value `bits`
+    pub const fn from_bits(bits: u8) -> Self {
+        DecodeModeConfig(bits)
+    }
+
+    /// return the register value
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// return the configuration with hexadecimal decode for digit pair `pair` (`0` ~ `7`)
+    /// turned on if `decode`, otherwise off
+    pub const fn with_pair(self, pair: u8, decode: bool) -> Self {
+        let bit = 1 << (pair & 0x07);
+        if decode {
+            DecodeModeConfig(self.0 | bit)
+        } else {
+            DecodeModeConfig(self.0 & !bit)
+        }
+    }
+
+    /// return `true` if digit pair `pair` (`0` ~ `7`) is decoded
+    pub fn is_decoded(self, pair: u8) -> bool {
+        self.0.get_bit((pair & 0x07) as usize)
+    }
+}
+
+impl From<DecodeMode> for DecodeModeConfig {
+    fn from(mode: DecodeMode) -> Self {
+        DecodeModeConfig(mode.value())
+    }
+}
+
+/// Scan Limit, the digits that are displayed
+#[derive(Clone, Copy)]
+pub enum ScanLimit {
+    Digit0 = 0x00,
+    Digits0To1 = 0x01,
+    Digits0To2 = 0x02,
+    Digits0To3 = 0x03,
+    Digits0To4 = 0x04,
+    Digits0To5 = 0x05,
+    Digits0To6 = 0x06,
+    Digits0To7 = 0x07,
+}
+
+impl ScanLimit {
+    /// return enum value as u8
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+
+    /// return the number of scanned digits
+    pub fn digits(self) -> usize {
+        self as usize + 1
+    }
+
+    /// return the scan limit of `digits` digits, clamped to `1` ~ `8`
+    pub fn from_digits(digits: usize) -> Self {
+        match digits {
+            0 | 1 => ScanLimit::Digit0,
+            2 => ScanLimit::Digits0To1,
+            3 => ScanLimit::Digits0To2,
+            4 => ScanLimit::Digits0To3,
+            5 => ScanLimit::Digits0To4,
+            6 => ScanLimit::Digits0To5,
+            7 => ScanLimit::Digits0To6,
+            _ => ScanLimit::Digits0To7,
+        }
+    }
+}
+
+/// Digit data plane. Plane P1 is displayed instead of plane P0 during the off phase of blinking.
+#[derive(Clone, Copy)]
+pub enum Plane {
+    P0,
+    P1,
+}
+
+impl Plane {
+    /// return the digit 0 register of the plane
+    pub fn digit0(self) -> Register {
+        match self {
+            Plane::P0 => Register::Digit0Plane0,
+            Plane::P1 => Register::Digit0Plane1,
+        }
+    }
+}
+
+/// Digit order of the text APIs
+#[derive(Clone, Copy)]
+pub enum Order {
+    /// The first character is shown on digit 0.
+    Normal,
+    /// The first character is shown on digit 7, for boards that wire digit 0 on the right.
+    Reversed,
+}
+
+impl Order {
+    /// return the digit that shows text position `position`
+    pub fn digit(self, position: usize) -> usize {
+        match self {
+            Order::Normal => position & 0x07,
+            Order::Reversed => 7 - (position & 0x07),
+        }
+    }
+}
+
+/// Mapping from text positions to physical digits
+#[derive(Clone, Copy)]
+pub struct DigitMap([u8; 8]);
+
+impl DigitMap {
+    /// Create a map. `map[position]` is the digit (`0` ~ `7`) that shows text position `position`.
+    ///
+    /// Returns `None` unless every digit appears exactly once.
+    pub fn new(map: [u8; 8]) -> Option<Self> {
+        if is_permutation(&map) {
+            Some(DigitMap(map))
+        } else {
+            None
+        }
+    }
+
+    /// return the digit that shows text position `position`
+    pub fn digit(self, position: usize) -> usize {
+        self.0[position & 0x07] as usize
+    }
+
+    /// Squeeze the map onto the first `digits` digits, keeping the relative order,
+    /// so that text positions below `digits` always land on scanned digits.
+    fn limit(self, digits: usize) -> DigitMap {
+        let mut map: [u8; 8] = [0; 8];
+        let scanned = self.0.iter().filter(|&&digit| (digit as usize) < digits);
+        let unscanned = self.0.iter().filter(|&&digit| (digit as usize) >= digits);
+        for (entry, &digit) in map.iter_mut().zip(scanned.chain(unscanned)) {
+            *entry = digit;
+        }
+        DigitMap(map)
+    }
+}
+
+impl From<Order> for DigitMap {
+    fn from(order: Order) -> Self {
+        let mut map: [u8; 8] = [0; 8];
+        for (i, digit) in map.iter_mut().enumerate() {
+            *digit = order.digit(i) as u8;
+        }
+        DigitMap(map)
+    }
+}
+
+/// Mapping from segment bits to the register bits that drive them
+#[derive(Clone, Copy)]
+pub struct SegmentMap([u8; 8]);
+
+impl SegmentMap {
+    /// Segments wired as in the datasheet
+    pub const IDENTITY: SegmentMap = SegmentMap([0, 1, 2, 3, 4, 5, 6, 7]);
+
+    /// Create a map. `map[bit]` is the register bit (`0` ~ `7`) that drives segment bit `bit`.
+    ///
+    /// Returns `None` unless every register bit appears exactly once.
+    pub fn new(map: [u8; 8]) -> Option<Self> {
+        if is_permutation(&map) {
+            Some(SegmentMap(map))
+        } else {
+            None
+        }
+    }
+
+    /// return segment data rearranged to the board wiring
+    pub fn apply(self, segments: u8) -> u8 {
+        let mut data: u8 = 0;
+        for (bit, &target) in self.0.iter().enumerate() {
+            data.set_bit(target as usize, segments.get_bit(bit));
+        }
+        data
+    }
+}
+
+/// Cursor behavior past the last scanned digit
+#[derive(Clone, Copy)]
+pub enum Wrap {
+    /// Continue at the first position.
+    Wrap,
+    /// Shift the text left and write at the last position.
+    Scroll,
+    /// Ignore further characters until the cursor is moved.
+    Stop,
+}
+
+/// What to show when text does not fit the scanned digits
+#[derive(Clone, Copy)]
+pub enum Overflow {
+    /// Show as much of the text as fits.
+    Truncate,
+    /// Fill every digit with the character, e.g. `'-'` or `'E'`.
+    Fill(char),
+}
+
+impl Overflow {
+    /// replace `digits` with the overflow indicator
+    fn apply(self, digits: &mut [u8; 8]) {
+        if let Overflow::Fill(c) = self {
+            *digits = [ascii(c); 8];
+        }
+    }
+}
+
+/// Padding of the numeric writes
+#[derive(Clone, Copy)]
+pub enum Padding {
+    /// Leading blanks, e.g. `"      42"`.
+    Blank,
+    /// Leading zeros up to the given number of digits, e.g. `"   00042"` for `Zeros(5)`.
+    /// A sign or prefix goes in front of the zeros.
+    Zeros(usize),
+}
+
+/// Thousands separator of the decimal numeric writes
+#[derive(Clone, Copy)]
+pub enum Separator {
+    /// No separator, e.g. `"1234567"`.
+    None,
+    /// Decimal point of the digit left of each group of three, e.g. `"1.234.567"`.
+    /// Numbers with decimals get no separator, since the decimal point uses the same segment.
+    Dot,
+    /// Apostrophe between groups of three, e.g. `"1'234'567"`.
+    Apostrophe,
+}
+
+/// Temperature unit
+#[derive(Clone, Copy)]
+pub enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+
+impl Unit {
+    /// return the unit letter
+    pub fn symbol(self) -> u8 {
+        match self {
+            Unit::Celsius => b'C',
+            Unit::Fahrenheit => b'F',
+        }
+    }
+}
+
+/// Layout of `write_time`
+#[derive(Clone, Copy)]
+pub enum TimeFormat {
+    /// Hours and minutes
+    Hmm,
+    /// Hours, minutes and seconds
+    Hms,
+}
+
+/// Layout of `write_date`
+#[derive(Clone, Copy)]
+pub enum DateFormat {
+    /// `"31.12.24"`, separated by decimal points
+    DdMmYy,
+    /// `"12-31-24"`
+    MmDdYy,
+    /// `"24-12-31"`
+    YyMmDd,
+}
+
+/// Intensity select of the configuration register
+#[derive(Clone, Copy, PartialEq)]
+pub enum IntensityMode {
+    /// The global intensity register applies to all digits.
+    Global,
+    /// Each digit uses its digit intensity register.
+    PerDigit,
+}
+
+impl IntensityMode {
+    /// return enum value as bool
+    pub fn value(self) -> bool {
+        match self {
+            IntensityMode::Global => false,
+            IntensityMode::PerDigit => true,
+        }
+    }
+}
+
+impl From<bool> for IntensityMode {
+    fn from(bit: bool) -> Self {
+        if bit {
+            IntensityMode::PerDigit
+        } else {
+            IntensityMode::Global
+        }
+    }
+}
+
+/// Digit intensity register bank
+#[derive(Clone, Copy)]
+pub enum IntensityBank {
+    /// Intensity10 ~ Intensity76, digits 0 ~ 7
+    Primary,
+    /// Intensity10a ~ Intensity76a, digits 0a ~ 7a of the register map (Table 7)
+    Secondary,
+}
+
+/// Mapping of perceived brightness onto intensity levels
+#[derive(Clone, Copy)]
+pub enum BrightnessCurve {
+    /// Evenly spaced duty cycles
+    Linear,
+    /// Gamma 2 correction, so that evenly spaced percentages look evenly spaced
+    Gamma,
+}
+
+/// Intensity calibration of one device, for matching chips with different LED bins
+#[derive(Clone, Copy)]
+pub struct Calibration {
+    /// Scale in percent applied first
+    pub scale: u8,
+    /// Levels added after scaling
+    pub offset: i8,
+}
+
+impl Calibration {
+    /// No correction
+    pub const NONE: Calibration = Calibration {
+        scale: 100,
+        offset: 0,
+    };
+
+    /// return the calibrated intensity level for `level`, limited to `0` ~ `15`
+    pub fn apply(self, level: u8) -> u8 {
+        let scaled = (level as i32 * self.scale as i32 + 50) / 100;
+        (scaled + self.offset as i32).clamp(0, MAX_INTENSITY as i32) as u8
+    }
+
+    /// return the lowest level that `apply` maps closest to the register value `calibrated`
+    pub fn level(self, calibrated: u8) -> u8 {
+        (0..=MAX_INTENSITY)
+            .min_by_key(|&level| (self.apply(level) as i32 - calibrated as i32).abs())
+            .unwrap_or(0)
+    }
+}
+
+/// Blink settings decoded from the configuration register
+#[derive(Clone, Copy)]
+pub struct BlinkConfig {
+    /// Blink enable
+    pub mode: BlinkMode,
+    /// Blink rate
+    pub rate: BlinkRate,
+    /// Plane shown at the moment of reading
+    pub phase: BlinkPhase,
+}
+
+/// Key bank of eight keys
+#[derive(Clone, Copy)]
+pub enum KeyBank {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl KeyBank {
+    /// return the key mask/debounce register of the bank
+    pub fn mask_register(self) -> Register {
+        match self {
+            KeyBank::A => Register::KeyAMaskDebounce,
+            KeyBank::B => Register::KeyBMaskDebounce,
+            KeyBank::C => Register::KeyCMaskDebounce,
+            KeyBank::D => Register::KeyDMaskDebounce,
+        }
+    }
+}
+
+/// Number of scanned keys
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeyscanConfig {
+    /// No key scanning, ports P0 ~ P4 are GPIO
+    Disabled,
+    /// Keys A0 ~ A7
+    Keys8,
+    /// Keys A0 ~ B7
+    Keys16,
+    /// Keys A0 ~ C7
+    Keys24,
+    /// Keys A0 ~ D7
+    Keys32,
+}
+
+impl KeyscanConfig {
+    /// return the number of scanned key banks
+    pub fn banks(self) -> u8 {
+        self as u8
+    }
+
+    /// return the key masks enabling all scanned keys
+    pub fn mask(self) -> u32 {
+        match self {
+            KeyscanConfig::Disabled => 0,
+            _ => u32::MAX >> (8 * (4 - self as u32)),
+        }
+    }
+}
+
+/// Configuration register contents. see Table 17
+#[derive(Clone, Copy)]
+pub struct Config {
+    /// `true`: shutdown, `false`: normal operation
+    pub shutdown: bool,
+    /// Blink enable
+    pub blink: BlinkMode,
+    /// Blink rate
+    pub rate: BlinkRate,
+    /// Blink timing synchronization
+    pub sync: BlinkSync,
+    /// `true`: clear the digit data of both planes
+    pub clear: bool,
+    /// Global or per-digit intensity
+    pub intensity_mode: IntensityMode,
+    /// Plane shown at the moment of reading. Read-only.
+    pub phase: BlinkPhase,
+}
+
+impl Config {
+    /// Power-on configuration
+    pub const DEFAULT: Config = Config {
+        shutdown: true,
+        blink: BlinkMode::Disable,
+        rate: BlinkRate::Slow,
+        sync: BlinkSync::Unaffected,
+        clear: false,
+        intensity_mode: IntensityMode::Global,
+        phase: BlinkPhase::P1,
+    };
+
+    /// return the configuration register value. `phase` is read-only and not included.
+    pub fn value(self) -> u8 {
+        let mut config: u8 = 0;
+        config.set_bit(ConfigBitFlag::Shutdown.value(), !self.shutdown);
+        config.set_bit(ConfigBitFlag::Blink.value(), self.blink.value());
+        config.set_bit(ConfigBitFlag::BlinkRate.value(), self.rate.value());
+        config.set_bit(ConfigBitFlag::BlinkTiming.value(), self.sync.value());
+        config.set_bit(ConfigBitFlag::ClearDigit.value(), self.clear);
+        config.set_bit(
+            ConfigBitFlag::Intensity.value(),
+            self.intensity_mode.value(),
+        );
+        config
+    }
+}
+
+impl From<u8> for Config {
+    fn from(config: u8) -> Self {
+        Config {
+            shutdown: !config.get_bit(ConfigBitFlag::Shutdown.value()),
+            blink: BlinkMode::from(config.get_bit(ConfigBitFlag::Blink.value())),
+            rate: BlinkRate::from(config.get_bit(ConfigBitFlag::BlinkRate.value())),
+            sync: BlinkSync::from(config.get_bit(ConfigBitFlag::BlinkTiming.value())),
+            clear: config.get_bit(ConfigBitFlag::ClearDigit.value()),
+            intensity_mode: IntensityMode::from(config.get_bit(ConfigBitFlag::Intensity.value())),
+            phase: BlinkPhase::from(config.get_bit(ConfigBitFlag::BlinkPhase.value())),
+        }
+    }
+}
+
+/// Settings of `initialize`
+#[derive(Clone, Copy)]
+pub struct InitConfig {
+    /// Decode mode of the digit pairs
+    pub decode_mode: DecodeModeConfig,
+    /// Type of each digit
+    pub digit_type: DigitTypeConfig,
+    /// Scanned digits
+    pub scan_limit: ScanLimit,
+    /// Global intensity level `0` ~ `15`
+    pub intensity: u8,
+}
+
+impl InitConfig {
+    /// No decode, 16-segment or 7-segment digits, eight digits at full intensity
+    pub const DEFAULT: InitConfig = InitConfig {
+        decode_mode: DecodeModeConfig::NO_DECODE,
+        digit_type: DigitTypeConfig::SEG7_16,
+        scan_limit: ScanLimit::Digits0To7,
+        intensity: MAX_INTENSITY,
+    };
+}
+
+/// Port configuration register contents. see Table 22
+#[derive(Clone, Copy)]
+pub struct PortConfig {
+    /// Direction of ports P0 ~ P4
+    pub pins: [PinMode; 5],
+    /// Scanned keys. Port P0 is the key interrupt output unless `KeyscanConfig::Disabled`.
+    pub keyscan: KeyscanConfig,
+}
+
+impl PortConfig {
+    /// return the port configuration register value
+    pub fn value(self) -> u8 {
+        let mut config: u8 = 0;
+        for (port, &mode) in self.pins.iter().enumerate() {
+            config.set_bit(port, mode == PinMode::Input);
+        }
+        config.set_bits(keys::KEYSCAN_SIZE, self.keyscan.banks());
+        config
+    }
+
+    /// return whether port P0 is the key interrupt output
+    pub fn irq(self) -> bool {
+        self.keyscan != KeyscanConfig::Disabled
+    }
+}
+
+impl From<u8> for PortConfig {
+    fn from(config: u8) -> Self {
+        let mut pins = [PinMode::Output; 5];
+        for (port, mode) in pins.iter_mut().enumerate() {
+            if config.get_bit(port) {
+                *mode = PinMode::Input;
+            }
+        }
+        let keyscan = match config.get_bits(keys::KEYSCAN_SIZE) {
+            0 => KeyscanConfig::Disabled,
+            1 => KeyscanConfig::Keys8,
+            2 => KeyscanConfig::Keys16,
+            3 => KeyscanConfig::Keys24,
+            _ => KeyscanConfig::Keys32,
+        };
+        PortConfig { pins, keyscan }
+    }
+}
+
+/// Keys reported by `read_key_interrupt`, key A0 in bit 0 ~ key D7 in bit 31
+#[derive(Clone, Copy)]
+pub struct KeyInterrupt {
+    /// Debounced presses of masked keys since the previous read, the cause of the interrupt
+    pub debounced: u32,
+    /// Keys still down after the interrupt was cleared
+    pub pressed: u32,
+}
+
+/// Error of `wait_for_key`
+#[derive(Debug)]
+pub enum KeyWaitError<E, PE> {
+    /// Bus error
+    Bus(E),
+    /// IRQ pin error
+    Pin(PE),
+}
+
+/// Digit planes to clear
+#[derive(Clone, Copy)]
+pub enum ClearScope {
+    Plane0,
+    Plane1,
+    Both,
+}
+
+/// Blink timing synchronization bit of the configuration register
+#[derive(Clone, Copy)]
+pub enum BlinkSync {
+    /// Blink timing counters are unaffected.
+    Unaffected,
+    /// Blink timing counters are reset.
+    Reset,
+}
+
+impl BlinkSync {
+    /// return enum value as bool
+    pub fn value(self) -> bool {
+        match self {
+            BlinkSync::Unaffected => false,
+            BlinkSync::Reset => true,
+        }
+    }
+}
+
+impl From<bool> for BlinkSync {
+    fn from(bit: bool) -> Self {
+        if bit {
+            BlinkSync::Reset
+        } else {
+            BlinkSync::Unaffected
+        }
+    }
+}
+
+/// Blink phase readback of the configuration register
+#[derive(Clone, Copy, PartialEq)]
+pub enum BlinkPhase {
+    /// Plane P0 is displayed.
+    P0,
+    /// Plane P1 is displayed.
+    P1,
+}
+
+impl From<bool> for BlinkPhase {
+    fn from(bit: bool) -> Self {
+        if bit {
+            BlinkPhase::P0
+        } else {
+            BlinkPhase::P1
+        }
+    }
+}
+
+/// Pin Mode Input/Output
+#[derive(Clone, Copy, PartialEq)]
+pub enum PinMode {
+    Input,
+    Output,
+}
+
+/// Blink Mode Enable/Disable
+#[derive(Clone, Copy)]
+pub enum BlinkMode {
+    Disable,
+    Enable,
+}
+
+impl BlinkMode {
+    /// return enum value as bool
+    pub fn value(self) -> bool {
+        match self {
+            BlinkMode::Disable => false,
+            BlinkMode::Enable => true,
+        }
+    }
+}
+
+impl From<bool> for BlinkMode {
+    fn from(bit: bool) -> Self {
+        if bit {
+            BlinkMode::Enable
+        } else {
+            BlinkMode::Disable
+        }
+    }
+}
+
+/// Blink Rate Fast/Slow
+#[derive(Clone, Copy)]
+pub enum BlinkRate {
+    Fast,
+    Slow,
+}
+
+impl BlinkRate {
+    /// return enum value as bool
+    pub fn value(self) -> bool {
+        match self {
+            BlinkRate::Slow => false,
+            BlinkRate::Fast => true,
+        }
+    }
+}
+
+impl From<bool> for BlinkRate {
+    fn from(bit: bool) -> Self {
+        if bit {
+            BlinkRate::Fast
+        } else {
+            BlinkRate::Slow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock::Mock;
+
+    fn max6955(mock: Mock) -> Max6955<Mock> {
+        Max6955::new(mock).unwrap()
+    }
+
+    #[test]
+    fn clear_is_not_repeated_after_apply_config() {
+        let mut max6955 = max6955(Mock::new());
+        let config = Config {
+            clear: true,
+            ..Config::DEFAULT
+        };
+        max6955.apply_config(config).unwrap();
+        max6955.write_str("12").unwrap();
+        max6955
+            .set_blink(BlinkMode::Enable, BlinkRate::Slow)
+            .unwrap();
+        assert_eq!(max6955.i2c.config_writes()[0] & 0x20, 0x20);
+        assert_eq!(max6955.i2c.last_config(), 0x08);
+    }
+
+    #[test]
+    fn transient_bits_read_from_the_device_are_not_written_back() {
+        let mut max6955 = max6955(Mock::with(Register::Configuration, 0xB0));
+        max6955.powerup().unwrap();
+        assert_eq!(max6955.i2c.last_config(), 0x01);
+        max6955.i2c.registers[Register::Configuration.addr() as usize] = 0xB1;
+        max6955.configuration().unwrap();
+        max6955
+            .set_blink(BlinkMode::Enable, BlinkRate::Fast)
+            .unwrap();
+        assert_eq!(max6955.i2c.last_config(), 0x0D);
+    }
+
+    #[test]
+    fn intensity_mode_is_read_from_the_device() {
+        let mut max6955 = max6955(Mock::with(Register::Configuration, 0x41));
+        max6955.set_global_intensity(5).unwrap();
+        assert_eq!(max6955.i2c.get(Register::Configuration), 0x01);
+        max6955.i2c.registers[Register::Configuration.addr() as usize] = 0x41;
+        max6955.resync().unwrap();
+        max6955.set_global_intensity(5).unwrap();
+        assert_eq!(max6955.i2c.get(Register::Configuration), 0x01);
+    }
+
+    #[test]
+    fn intensity_reads_back_before_calibration() {
+        let mut max6955 = max6955(Mock::new());
+        max6955.set_calibration(Calibration {
+            scale: 100,
+            offset: 2,
+        });
+        max6955.set_global_intensity(7).unwrap();
+        assert_eq!(max6955.i2c.get(Register::GlobalIntensity), 9);
+        assert_eq!(max6955.global_intensity().unwrap(), 7);
+        max6955.set_digit_intensity(3, 4).unwrap();
+        assert_eq!(max6955.digit_intensity(3).unwrap(), 4);
+    }
+
+    #[test]
+    fn pin_mode_keeps_the_port_configuration() {
+        let mut max6955 = max6955(Mock::with(Register::PortConfiguration, 0b1110_0011));
+        max6955.set_pin_mode(2, PinMode::Input).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b1110_0111);
+        max6955.set_pin_mode(0, PinMode::Output).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b1110_0110);
+        max6955.set_pin_mode(5, PinMode::Output).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b1110_0110);
+    }
+
+    #[test]
+    fn key_settings_keep_the_port_configuration() {
+        let mut max6955 = max6955(Mock::with(Register::PortConfiguration, 0b0000_0011));
+        max6955.enable_key_interrupt(true).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b0010_0011);
+        max6955.set_keyscan(KeyscanConfig::Keys16).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b0100_0011);
+        max6955.enable_key_interrupt(true).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b0100_0011);
+        max6955.set_keyscan(KeyscanConfig::Disabled).unwrap();
+        assert_eq!(max6955.i2c.get(Register::PortConfiguration), 0b0000_0011);
+        assert_eq!(max6955.key_masks(), 0);
+    }
+
+    #[test]
+    fn port_config_decodes_key_scanning() {
+        let config = PortConfig::from(0x00);
+        assert_eq!(config.keyscan, KeyscanConfig::Disabled);
+        assert!(!config.irq());
+        let config = PortConfig::from(0b1000_0001);
+        assert_eq!(config.keyscan, KeyscanConfig::Keys32);
+        assert!(config.irq());
+        assert_eq!(config.value(), 0b1000_0001);
+        assert_eq!(KeyscanConfig::Keys24.mask(), 0x00FF_FFFF);
+        assert_eq!(KeyscanConfig::Keys32.mask(), u32::MAX);
+    }
+
+    #[test]
+    fn pin_writes_keep_the_other_outputs() {
+        let mut max6955 = max6955(Mock::with(Register::GpioData, 0b1_0001));
+        max6955.set_pin(1, true).unwrap();
+        assert_eq!(max6955.i2c.get(Register::GpioData), 0b1_0011);
+        max6955.toggle_pin(4).unwrap();
+        assert_eq!(max6955.i2c.get(Register::GpioData), 0b0_0011);
+        max6955.toggle_pin(13).unwrap();
+        assert_eq!(max6955.i2c.get(Register::GpioData), 0b0_0011);
+    }
+
+    #[test]
+    fn reset_to_defaults_scans_all_digits() {
+        let mut max6955 = max6955(Mock::new());
+        max6955.reset_to_defaults().unwrap();
+        assert_eq!(max6955.i2c.get(Register::ScanLimit), 0x07);
+        max6955.write_str("ABCDEFGH").unwrap();
+        assert_eq!(&max6955.i2c.registers[0x20..0x28], b"ABCDEFGH");
+    }
+
+    #[test]
+    fn write_time_keeps_blinking_digits() {
+        let mut max6955 = max6955(Mock::new());
+        max6955.set_edit_cursor(Some(4), BlinkRate::Fast).unwrap();
+        max6955
+            .write_time(12, 34, 0, TimeFormat::Hmm, false)
+            .unwrap();
+        assert_eq!(max6955.i2c.get(Register::Digit4Plane0), b'1');
+        assert_eq!(max6955.i2c.get(Register::Digit4Plane1), b' ');
+        assert_eq!(
+            max6955.i2c.get(Register::Digit5Plane1),
+            b'2' | DECIMAL_POINT
+        );
+        max6955
+            .write_time(12, 34, 0, TimeFormat::Hmm, true)
+            .unwrap();
+        assert_eq!(max6955.i2c.get(Register::Digit4Plane1), b' ');
+        assert_eq!(max6955.i2c.get(Register::Digit5Plane1), b'2');
+    }
+
+    #[test]
+    fn decoded_writes_leave_the_text_alone() {
+        let mut max6955 = max6955(Mock::new());
+        max6955.write_str("ABCDEFGH").unwrap();
+        max6955.set_digit_blink(1, true).unwrap();
+        max6955.write_decoded_u32(0x1234_ABCD).unwrap();
+        let digits = [0x1, 0x2, 0x3, 0x4, 0xA, 0xB, 0xC, 0xD];
+        for (i, &digit) in digits.iter().enumerate() {
+            assert_eq!(
+                max6955.i2c.registers[Plane::P0.digit0().addr() as usize + i],
+                digit
+            );
+            assert_eq!(
+                max6955.i2c.registers[Plane::P1.digit0().addr() as usize + i],
+                digit
+            );
+        }
+        assert_eq!(max6955.current_text(), "ABCDEFGH");
+    }
+
+    #[test]
+    fn clearing_both_planes_writes_the_configuration_once() {
+        let mut max6955 = max6955(Mock::with(Register::Configuration, 0x01));
+        max6955.clear(ClearScope::Both).unwrap();
+        assert_eq!(max6955.i2c.config_writes(), &[0x21]);
+        assert_eq!(max6955.config().unwrap(), 0x01);
+    }
+
+    #[test]
+    fn digit_map_limit_keeps_the_order_of_scanned_digits() {
+        let reversed = DigitMap::from(Order::Reversed);
+        assert_eq!(reversed.limit(4).0, [3, 2, 1, 0, 7, 6, 5, 4]);
+        assert_eq!(reversed.limit(8).0, reversed.0);
+        let map = DigitMap::new([5, 0, 6, 1, 7, 2, 3, 4]).unwrap();
+        assert_eq!(map.limit(3).0, [0, 1, 2, 5, 6, 7, 3, 4]);
+        assert_eq!(map.limit(1).0, [0, 5, 6, 1, 7, 2, 3, 4]);
+    }
+
+    #[test]
+    fn segment_map_moves_each_segment_bit() {
+        assert_eq!(SegmentMap::IDENTITY.apply(0b1010_0101), 0b1010_0101);
+        let map = SegmentMap::new([7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+        assert_eq!(map.apply(0b0000_0011), 0b1100_0000);
+        let map = SegmentMap::new([1, 2, 3, 4, 5, 6, 0, 7]).unwrap();
+        assert_eq!(map.apply(0b0100_0001), 0b0000_0011);
+        assert_eq!(map.apply(DECIMAL_POINT), DECIMAL_POINT);
+        assert!(SegmentMap::new([0, 0, 1, 2, 3, 4, 5, 6]).is_none());
+    }
+
+    #[test]
+    fn out_of_range_digits_are_masked() {
+        let mut max6955 = max6955(Mock::new());
+        max6955.set_digit_blink(9, true).unwrap();
+        max6955.highlight_digit(12, true).unwrap();
+        max6955.set_digit_intensity(8, 3).unwrap();
+        assert_eq!(max6955.blink_mask, 0b10);
     }
 }
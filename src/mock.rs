@@ -0,0 +1,73 @@
+//! Register file standing in for a MAX6955 in unit tests
+
+use super::{Interface, Register};
+use hal::blocking::delay::DelayMs;
+
+/// Registers of one device and a log of the configuration register writes
+pub(crate) struct Mock {
+    pub(crate) registers: [u8; 0x80],
+    config_writes: [u8; 32],
+    config_count: usize,
+}
+
+impl Mock {
+    /// Device with all registers `0`
+    pub(crate) fn new() -> Self {
+        Mock {
+            registers: [0; 0x80],
+            config_writes: [0; 32],
+            config_count: 0,
+        }
+    }
+
+    /// Device with `value` in register `reg`
+    pub(crate) fn with(reg: Register, value: u8) -> Self {
+        let mut mock = Mock::new();
+        mock.registers[reg.addr() as usize] = value;
+        mock
+    }
+
+    /// Register value
+    pub(crate) fn get(&self, reg: Register) -> u8 {
+        self.registers[reg.addr() as usize]
+    }
+
+    /// Values written to the configuration register, oldest first
+    pub(crate) fn config_writes(&self) -> &[u8] {
+        &self.config_writes[..self.config_count]
+    }
+
+    /// Last value written to the configuration register
+    pub(crate) fn last_config(&self) -> u8 {
+        self.config_writes()[self.config_count - 1]
+    }
+}
+
+impl Interface for Mock {
+    type Error = ();
+
+    fn write_registers(&mut self, _addr: u8, reg: u8, values: &[u8]) -> Result<(), ()> {
+        for (i, &value) in values.iter().enumerate() {
+            let reg = reg as usize + i;
+            if reg == Register::Configuration.addr() as usize {
+                self.config_writes[self.config_count] = value;
+                self.config_count += 1;
+            }
+            self.registers[reg] = value;
+        }
+        Ok(())
+    }
+
+    fn read_registers(&mut self, _addr: u8, reg: u8, buffer: &mut [u8]) -> Result<(), ()> {
+        let reg = reg as usize;
+        buffer.copy_from_slice(&self.registers[reg..reg + buffer.len()]);
+        Ok(())
+    }
+}
+
+/// Delay that returns at once
+pub(crate) struct NoDelay;
+
+impl DelayMs<u32> for NoDelay {
+    fn delay_ms(&mut self, _ms: u32) {}
+}
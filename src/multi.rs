@@ -0,0 +1,123 @@
+//! Logical display spanning several MAX6955s
+
+use super::{ascii, BlinkMode, BlinkRate, BlinkSync, ConfigBitFlag, Interface, Max6955, Register};
+use bit_field::BitField;
+
+/// `N` MAX6955s presented as one long display
+///
+/// Each chip needs its own address. Text starts on the first chip and continues on the next
+/// one once the scanned digits of a chip are filled.
+pub struct MultiMax6955<I2C, const N: usize> {
+    chips: [Max6955<I2C>; N],
+}
+
+impl<I2C, E, const N: usize> MultiMax6955<I2C, N>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Construct a logical display from drivers ordered from left to right
+    ///
+    /// # Arguments
+    ///
+    /// * `chips` - MAX6955 drivers with different addresses
+    pub fn new(chips: [Max6955<I2C>; N]) -> Self {
+        MultiMax6955 { chips }
+    }
+
+    /// Set Global Intensity of all chips
+    /// # Arguments
+    ///
+    /// * `intensity` - intensity level `0`: lowest ~ `15`: highest
+    pub fn set_global_intensity(&mut self, intensity: u8) -> Result<(), E> {
+        for chip in self.chips.iter_mut() {
+            chip.set_global_intensity(intensity)?;
+        }
+        Ok(())
+    }
+
+    /// Control Blinking of all chips
+    /// # Arguments
+    ///
+    /// * `mode` - `BlinkMode::Enable`: blink, `BlinkMode::Disable`: not blink
+    /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
+    pub fn set_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
+        for chip in self.chips.iter_mut() {
+            chip.set_blink(mode, rate)?;
+        }
+        Ok(())
+    }
+
+    /// Synchronize Blinking of all chips
+    ///
+    /// Sets blink enable and rate on every chip, then resets the blink timing counters of all
+    /// chips with back-to-back writes, so the whole display blinks in lockstep. The reset bit
+    /// is not kept in the chips' copies of the configuration, so later writes do not repeat it.
+    /// # Arguments
+    ///
+    /// * `mode` - `BlinkMode::Enable`: blink, `BlinkMode::Disable`: not blink
+    /// * `rate` - `BlinkRate::Fast`: 0.5s cycle, `BlinkRate::Slow`: 1.0s cycle
+    pub fn sync_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
+        self.set_blink(mode, rate)?;
+        let mut configs: [u8; N] = [0; N];
+        for (config, chip) in configs.iter_mut().zip(self.chips.iter_mut()) {
+            *config = chip.config()?;
+            config.set_bit(ConfigBitFlag::BlinkTiming.value(), BlinkSync::Reset.value());
+        }
+        for (config, chip) in configs.iter().zip(self.chips.iter_mut()) {
+            chip.write_register(Register::Configuration, *config)?;
+        }
+        Ok(())
+    }
+
+    /// Power up all chips
+    pub fn powerup(&mut self) -> Result<(), E> {
+        for chip in self.chips.iter_mut() {
+            chip.powerup()?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown all chips
+    pub fn shutdown(&mut self) -> Result<(), E> {
+        for chip in self.chips.iter_mut() {
+            chip.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Clear Display
+    pub fn clear_display(&mut self) -> Result<(), E> {
+        self.write_str("")
+    }
+
+    /// Write Text
+    /// # Arguments
+    /// * `text` - text to write across the chips. Characters beyond the last scanned digit are dropped.
+    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
+        self.write_iter(text.chars())
+    }
+
+    /// Write Characters
+    /// # Arguments
+    /// * `chars` - characters to write across the chips
+    pub fn write_iter(&mut self, mut chars: impl Iterator<Item = char>) -> Result<(), E> {
+        for chip in self.chips.iter_mut() {
+            let mut digits: [u8; 8] = [b' '; 8];
+            for (digit, c) in digits.iter_mut().take(chip.digits).zip(&mut chars) {
+                *digit = ascii(c);
+            }
+            chip.write_digits(&digits)?;
+        }
+        Ok(())
+    }
+
+    /// Access the individual drivers, ordered from left to right
+    pub fn chips(&mut self) -> &mut [Max6955<I2C>; N] {
+        &mut self.chips
+    }
+
+    /// Release the individual drivers
+    pub fn release(self) -> [Max6955<I2C>; N] {
+        self.chips
+    }
+}
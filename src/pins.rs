@@ -0,0 +1,121 @@
+//! embedded-hal pins on ports P0 ~ P4
+
+use super::{Interface, Max6955};
+use core::cell::{RefCell, RefMut};
+use hal::digital::v2::{InputPin, OutputPin};
+
+/// One port of a MAX6955 shared through a `RefCell`, usable as an output or, when configured
+/// as an input with `set_pin_mode`, as an input
+///
+/// Each access borrows the driver for the duration of one register transfer, and fails with
+/// `PinError::Busy` if the driver is borrowed elsewhere at that moment.
+pub struct PortPin<'a, I2C> {
+    max6955: &'a RefCell<Max6955<I2C>>,
+    port: usize,
+}
+
+/// Error of `PortPin`
+#[derive(Debug)]
+pub enum PinError<E> {
+    /// Bus error
+    Bus(E),
+    /// The shared driver is borrowed elsewhere
+    Busy,
+}
+
+/// Ports P0 ~ P4 as separate pins
+pub struct Ports<'a, I2C> {
+    /// Port P0
+    pub p0: PortPin<'a, I2C>,
+    /// Port P1
+    pub p1: PortPin<'a, I2C>,
+    /// Port P2
+    pub p2: PortPin<'a, I2C>,
+    /// Port P3
+    pub p3: PortPin<'a, I2C>,
+    /// Port P4
+    pub p4: PortPin<'a, I2C>,
+}
+
+impl<'a, I2C> Ports<'a, I2C> {
+    /// Split the ports of a shared driver
+    /// # Arguments
+    /// * `max6955` - driver shared by the pins
+    pub fn new(max6955: &'a RefCell<Max6955<I2C>>) -> Self {
+        Ports {
+            p0: PortPin { max6955, port: 0 },
+            p1: PortPin { max6955, port: 1 },
+            p2: PortPin { max6955, port: 2 },
+            p3: PortPin { max6955, port: 3 },
+            p4: PortPin { max6955, port: 4 },
+        }
+    }
+}
+
+impl<'a, I2C> PortPin<'a, I2C> {
+    /// Port number `0` ~ `4`
+    pub fn port(&self) -> usize {
+        self.port
+    }
+
+    /// Borrow the shared driver
+    fn driver<E>(&self) -> Result<RefMut<'a, Max6955<I2C>>, PinError<E>> {
+        self.max6955.try_borrow_mut().map_err(|_| PinError::Busy)
+    }
+}
+
+impl<'a, I2C, E> OutputPin for PortPin<'a, I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    type Error = PinError<E>;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.driver()?
+            .set_pin(self.port, false)
+            .map_err(PinError::Bus)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.driver()?
+            .set_pin(self.port, true)
+            .map_err(PinError::Bus)
+    }
+}
+
+impl<'a, I2C, E> InputPin for PortPin<'a, I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    type Error = PinError<E>;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.driver()?.read_pin(self.port).map_err(PinError::Bus)
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PinError, Ports};
+    use core::cell::RefCell;
+    use hal::digital::v2::{InputPin, OutputPin};
+    use mock::Mock;
+    use {Max6955, Register};
+
+    #[test]
+    fn busy_driver_is_an_error() {
+        let max6955 = RefCell::new(Max6955::new(Mock::new()).unwrap());
+        let mut ports = Ports::new(&max6955);
+        ports.p2.set_high().unwrap();
+        {
+            let _held = max6955.borrow();
+            assert!(matches!(ports.p2.set_low(), Err(PinError::Busy)));
+            assert!(matches!(ports.p2.is_high(), Err(PinError::Busy)));
+        }
+        assert_eq!(max6955.borrow().i2c.get(Register::GpioData) & 0x04, 0x04);
+    }
+}
@@ -0,0 +1,161 @@
+//! Driver for displays with fewer than eight digits
+
+use super::{
+    ascii, BlinkMode, BlinkRate, BrightnessCurve, Calibration, Config, DecodeModeConfig, DigitMap,
+    DigitTypeConfig, IntensityMode, Interface, Max6955, Order, ScanLimit, SegmentMap,
+};
+
+/// MAX6955 driver for a display with `DIGITS` digits
+///
+/// The scan limit is set to `DIGITS` on construction, and text never reaches the unconnected digits.
+pub struct SizedMax6955<I2C, const DIGITS: usize> {
+    max6955: Max6955<I2C>,
+}
+
+impl<I2C, E, const DIGITS: usize> SizedMax6955<I2C, DIGITS>
+where
+    I2C: Interface<Error = E>,
+{
+    const VALID_DIGITS: () = assert!(DIGITS >= 1 && DIGITS <= 8, "DIGITS must be 1 ~ 8");
+
+    /// Construct a driver for a `DIGITS` digit display. `DIGITS` outside `1` ~ `8` fails to build.
+    ///
+    /// # Arguments
+    ///
+    /// * `max6955` - MAX6955 driver
+    ///
+    /// # Errors
+    ///
+    /// * `E` - returned in case there was an error writing the scan limit
+    ///
+    pub fn new(mut max6955: Max6955<I2C>) -> Result<Self, E> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_DIGITS;
+        max6955.set_scan_limit(ScanLimit::from_digits(DIGITS))?;
+        Ok(SizedMax6955 { max6955 })
+    }
+
+    /// Write Text
+    /// # Arguments
+    /// * `text` - text to write. Characters beyond `DIGITS` are dropped.
+    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
+        self.max6955.write_str(text)
+    }
+
+    /// Write ASCII Characters
+    /// # Arguments
+    /// * `chars` - exactly one ASCII character per digit
+    pub fn write_array(&mut self, chars: &[u8; DIGITS]) -> Result<(), E> {
+        let mut digits: [u8; 8] = [b' '; 8];
+        for (digit, &c) in digits.iter_mut().zip(chars.iter()) {
+            *digit = ascii(c as char);
+        }
+        self.max6955.write_digits(&digits)
+    }
+
+    /// Write Raw Segment Data
+    /// # Arguments
+    /// * `segments` - segment bits for each digit, see `Max6955::write_segments`
+    pub fn write_segments(&mut self, segments: &[u8; DIGITS]) -> Result<(), E> {
+        self.max6955.write_segments(segments)
+    }
+
+    /// Clear Display
+    pub fn clear_display(&mut self) -> Result<(), E> {
+        self.max6955.clear_display()
+    }
+
+    /// Set Digit Order, see `Max6955::set_digit_order`
+    pub fn set_digit_order(&mut self, order: Order) {
+        self.max6955.set_digit_order(order)
+    }
+
+    /// Set Digit Map, see `Max6955::set_digit_map`
+    pub fn set_digit_map(&mut self, map: DigitMap) {
+        self.max6955.set_digit_map(map)
+    }
+
+    /// Set Segment Map, see `Max6955::set_segment_map`
+    pub fn set_segment_map(&mut self, map: SegmentMap) {
+        self.max6955.set_segment_map(map)
+    }
+
+    /// Set Global Intensity, see `Max6955::set_global_intensity`
+    pub fn set_global_intensity(&mut self, intensity: u8) -> Result<(), E> {
+        self.max6955.set_global_intensity(intensity)
+    }
+
+    /// Control Blinking, see `Max6955::set_blink`
+    pub fn set_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_blink(mode, rate)
+    }
+
+    /// Power up Display, see `Max6955::powerup`
+    pub fn powerup(&mut self) -> Result<(), E> {
+        self.max6955.powerup()
+    }
+
+    /// Shutdown Display, see `Max6955::shutdown`
+    pub fn shutdown(&mut self) -> Result<(), E> {
+        self.max6955.shutdown()
+    }
+
+    /// Read Configuration, see `Max6955::configuration`
+    pub fn configuration(&mut self) -> Result<Config, E> {
+        self.max6955.configuration()
+    }
+
+    /// Configure Digit Type, see `Max6955::set_digit_type`
+    pub fn set_digit_type<T: Into<DigitTypeConfig>>(&mut self, digit_type: T) -> Result<(), E> {
+        self.max6955.set_digit_type(digit_type)
+    }
+
+    /// Configure Decode Mode, see `Max6955::set_decode_mode`
+    pub fn set_decode_mode<T: Into<DecodeModeConfig>>(&mut self, mode: T) -> Result<(), E> {
+        self.max6955.set_decode_mode(mode)
+    }
+
+    /// Select Global or Per-Digit Intensity, see `Max6955::set_intensity_mode`
+    pub fn set_intensity_mode(&mut self, mode: IntensityMode) -> Result<(), E> {
+        self.max6955.set_intensity_mode(mode)
+    }
+
+    /// Set Digit Intensity, see `Max6955::set_digit_intensity`
+    pub fn set_digit_intensity(&mut self, digit: u8, level: u8) -> Result<(), E> {
+        self.max6955.set_digit_intensity(digit, level)
+    }
+
+    /// Set Intensity Calibration, see `Max6955::set_calibration`
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.max6955.set_calibration(calibration)
+    }
+
+    /// Set the brightness curve of the percent and fade APIs, see `Max6955::set_brightness_curve`
+    pub fn set_brightness_curve(&mut self, curve: BrightnessCurve) {
+        self.max6955.set_brightness_curve(curve)
+    }
+
+    /// Release the underlying driver
+    pub fn release(self) -> Max6955<I2C> {
+        self.max6955
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SizedMax6955;
+    use mock::Mock;
+    use {Max6955, Register};
+
+    #[test]
+    fn configuration_keeps_the_scan_limit() {
+        let max6955 = Max6955::new(Mock::new()).unwrap();
+        let mut sized: SizedMax6955<_, 4> = SizedMax6955::new(max6955).unwrap();
+        sized.set_global_intensity(3).unwrap();
+        sized.powerup().unwrap();
+        sized.write_str("ABCDEF").unwrap();
+        let max6955 = sized.release();
+        assert_eq!(max6955.i2c.get(Register::ScanLimit), 3);
+        assert_eq!(max6955.current_text(), "ABCD");
+    }
+}
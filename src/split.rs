@@ -0,0 +1,622 @@
+//! Separate drivers for the display, key scan and GPIO parts of one MAX6955
+
+use super::{
+    BlinkConfig, BlinkMode, BlinkPhase, BlinkRate, BlinkSync, BrightnessCurve, Calibration,
+    ClearScope, Config, DateFormat, DecodeModeConfig, DigitMap, DigitTypeConfig, InitConfig,
+    IntensityBank, IntensityMode, Interface, KeyBank, KeyInterrupt, KeyWaitError, Max6955, Order,
+    Overflow, Padding, PinMode, Plane, PortConfig, ScanLimit, SegmentMap, SelfTest, Separator,
+    TimeFormat, Unit, Wrap,
+};
+#[cfg(feature = "fixed")]
+use fixed::{types::extra::LeEqU32, FixedI32};
+use hal::blocking::delay::DelayMs;
+use hal::digital::v2::InputPin;
+
+/// Drivers returned by `Max6955::split`
+pub struct Parts<I2C, K, G> {
+    /// Display driver
+    pub display: Display<I2C>,
+    /// Key scan driver
+    pub keys: KeyScan<K>,
+    /// GPIO driver, also owning the port configuration register
+    pub gpio: Gpio<G>,
+}
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Split into display, key scan and GPIO drivers
+    ///
+    /// Each part gets its own bus handle, e.g. proxies of a shared bus, so that the parts can
+    /// be moved to different tasks. The key masks, port configuration and output levels known
+    /// to this driver go with the parts.
+    /// # Arguments
+    ///
+    /// * `keys_i2c` - I2C interface of the key scan driver
+    /// * `gpio_i2c` - I2C interface of the GPIO driver
+    pub fn split<K, G>(self, keys_i2c: K, gpio_i2c: G) -> Parts<I2C, K, G> {
+        let mut keys = Max6955::bare(keys_i2c, self.addr);
+        keys.key_masks = self.key_masks;
+        let mut gpio = Max6955::bare(gpio_i2c, self.addr);
+        gpio.port_config = self.port_config;
+        gpio.outputs = self.outputs;
+        Parts {
+            display: Display { max6955: self },
+            keys: KeyScan { max6955: keys },
+            gpio: Gpio { max6955: gpio },
+        }
+    }
+}
+
+/// Display part of a MAX6955, see `Max6955::split`
+///
+/// Writes text and numbers and controls blinking, intensity and power. The key scan and GPIO
+/// registers are left to the other parts.
+pub struct Display<I2C> {
+    max6955: Max6955<I2C>,
+}
+
+impl<I2C, E> Display<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Set Digit Order, see `Max6955::set_digit_order`
+    pub fn set_digit_order(&mut self, order: Order) {
+        self.max6955.set_digit_order(order)
+    }
+
+    /// Set Digit Map, see `Max6955::set_digit_map`
+    pub fn set_digit_map(&mut self, map: DigitMap) {
+        self.max6955.set_digit_map(map)
+    }
+
+    /// Set Segment Map, see `Max6955::set_segment_map`
+    pub fn set_segment_map(&mut self, map: SegmentMap) {
+        self.max6955.set_segment_map(map)
+    }
+
+    /// Set Scan Limit, see `Max6955::set_scan_limit`
+    pub fn set_scan_limit(&mut self, limit: ScanLimit) -> Result<(), E> {
+        self.max6955.set_scan_limit(limit)
+    }
+
+    /// Set Global Intensity, see `Max6955::set_global_intensity`
+    pub fn set_global_intensity(&mut self, intensity: u8) -> Result<(), E> {
+        self.max6955.set_global_intensity(intensity)
+    }
+
+    /// Control Blinking, see `Max6955::set_blink`
+    pub fn set_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_blink(mode, rate)
+    }
+
+    /// Initialize the Display, see `Max6955::initialize`
+    pub fn initialize(&mut self, config: InitConfig) -> Result<(), E> {
+        self.max6955.initialize(config)
+    }
+
+    /// Power up Display, see `Max6955::powerup`
+    pub fn powerup(&mut self) -> Result<(), E> {
+        self.max6955.powerup()
+    }
+
+    /// Shutdown Display, see `Max6955::shutdown`
+    pub fn shutdown(&mut self) -> Result<(), E> {
+        self.max6955.shutdown()
+    }
+
+    /// Read Configuration, see `Max6955::configuration`
+    pub fn configuration(&mut self) -> Result<Config, E> {
+        self.max6955.configuration()
+    }
+
+    /// Read whether the display is powered up, see `Max6955::is_powered`
+    pub fn is_powered(&mut self) -> Result<bool, E> {
+        self.max6955.is_powered()
+    }
+
+    /// Read whether blinking is enabled, see `Max6955::is_blinking`
+    pub fn is_blinking(&mut self) -> Result<bool, E> {
+        self.max6955.is_blinking()
+    }
+
+    /// Read whether the display test is on, see `Max6955::in_display_test`
+    pub fn in_display_test(&mut self) -> Result<bool, E> {
+        self.max6955.in_display_test()
+    }
+
+    /// Apply Configuration in one write, see `Max6955::apply_config`
+    pub fn apply_config(&mut self, config: Config) -> Result<(), E> {
+        self.max6955.apply_config(config)
+    }
+
+    /// Configure Digit Type, see `Max6955::set_digit_type`
+    pub fn set_digit_type<T: Into<DigitTypeConfig>>(&mut self, digit_type: T) -> Result<(), E> {
+        self.max6955.set_digit_type(digit_type)
+    }
+
+    /// Configure Decode Mode, see `Max6955::set_decode_mode`
+    pub fn set_decode_mode<T: Into<DecodeModeConfig>>(&mut self, mode: T) -> Result<(), E> {
+        self.max6955.set_decode_mode(mode)
+    }
+
+    /// Display Test function, see `Max6955::test`
+    pub fn test(&mut self, enable: bool) -> Result<(), E> {
+        self.max6955.test(enable)
+    }
+
+    /// Timed Display Test, see `Max6955::test_for`
+    pub fn test_for<D: DelayMs<u32>>(&mut self, duration_ms: u32, delay: &mut D) -> Result<(), E> {
+        self.max6955.test_for(duration_ms, delay)
+    }
+
+    /// Communication Self Test, see `Max6955::self_test`
+    pub fn self_test(&mut self) -> Result<SelfTest, E> {
+        self.max6955.self_test()
+    }
+
+    /// Clear Display, see `Max6955::clear_display`
+    pub fn clear_display(&mut self) -> Result<(), E> {
+        self.max6955.clear_display()
+    }
+
+    /// Clear Digit Data, see `Max6955::clear`
+    pub fn clear(&mut self, scope: ClearScope) -> Result<(), E> {
+        self.max6955.clear(scope)
+    }
+
+    /// Clear one Digit Plane, see `Max6955::clear_plane`
+    pub fn clear_plane(&mut self, plane: Plane) -> Result<(), E> {
+        self.max6955.clear_plane(plane)
+    }
+
+    /// Set Cursor Position for `write_char`, see `Max6955::set_cursor`
+    pub fn set_cursor(&mut self, position: usize) {
+        self.max6955.set_cursor(position)
+    }
+
+    /// Current cursor position, see `Max6955::cursor`
+    pub fn cursor(&self) -> usize {
+        self.max6955.cursor()
+    }
+
+    /// Set Wrap Behavior, see `Max6955::set_wrap`
+    pub fn set_wrap(&mut self, wrap: Wrap) {
+        self.max6955.set_wrap(wrap)
+    }
+
+    /// Write a character at the cursor and advance the cursor, see `Max6955::write_char`
+    pub fn write_char(&mut self, c: char) -> Result<(), E> {
+        self.max6955.write_char(c)
+    }
+
+    /// Write Text, see `Max6955::write_str`
+    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
+        self.max6955.write_str(text)
+    }
+
+    /// Write Characters, see `Max6955::write_iter`
+    pub fn write_iter(&mut self, chars: impl Iterator<Item = char>) -> Result<(), E> {
+        self.max6955.write_iter(chars)
+    }
+
+    /// Write ASCII Text, see `Max6955::write_ascii`
+    pub fn write_ascii(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.max6955.write_ascii(bytes)
+    }
+
+    /// Set Overflow Behavior, see `Max6955::set_overflow`
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.max6955.set_overflow(overflow)
+    }
+
+    /// Set how the numeric writes pad numbers, see `Max6955::set_padding`
+    pub fn set_padding(&mut self, padding: Padding) {
+        self.max6955.set_padding(padding)
+    }
+
+    /// Set the thousands separator of decimal numeric writes, see `Max6955::set_separator`
+    pub fn set_separator(&mut self, separator: Separator) {
+        self.max6955.set_separator(separator)
+    }
+
+    /// Write Unsigned Integer, see `Max6955::write_u32`
+    pub fn write_u32(&mut self, value: u32) -> Result<(), E> {
+        self.max6955.write_u32(value)
+    }
+
+    /// Write Signed Integer, see `Max6955::write_i32`
+    pub fn write_i32(&mut self, value: i32) -> Result<(), E> {
+        self.max6955.write_i32(value)
+    }
+
+    /// Write Floating Point Number, see `Max6955::write_f32`
+    pub fn write_f32(&mut self, value: f32, decimals: usize) -> Result<(), E> {
+        self.max6955.write_f32(value, decimals)
+    }
+
+    /// Write Fixed-Point Number, see `Max6955::write_fixed`
+    #[cfg(feature = "fixed")]
+    pub fn write_fixed<Frac: LeEqU32>(
+        &mut self,
+        value: FixedI32<Frac>,
+        decimals: usize,
+    ) -> Result<(), E> {
+        self.max6955.write_fixed(value, decimals)
+    }
+
+    /// Write Temperature, see `Max6955::write_temperature`
+    pub fn write_temperature(&mut self, value_c_x10: i16, unit: Unit) -> Result<(), E> {
+        self.max6955.write_temperature(value_c_x10, unit)
+    }
+
+    /// Write Time, see `Max6955::write_time`
+    pub fn write_time(
+        &mut self,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        format: TimeFormat,
+        blink_colon: bool,
+    ) -> Result<(), E> {
+        self.max6955
+            .write_time(hours, minutes, seconds, format, blink_colon)
+    }
+
+    /// Write Date, see `Max6955::write_date`
+    pub fn write_date(
+        &mut self,
+        day: u8,
+        month: u8,
+        year: u16,
+        format: DateFormat,
+    ) -> Result<(), E> {
+        self.max6955.write_date(day, month, year, format)
+    }
+
+    /// Write Value with SI Prefix, see `Max6955::write_engineering`
+    pub fn write_engineering(&mut self, value: f32, unit: &str) -> Result<(), E> {
+        self.max6955.write_engineering(value, unit)
+    }
+
+    /// Write Hexadecimal Number, see `Max6955::write_hex_u32`
+    pub fn write_hex_u32(&mut self, value: u32, prefix: bool) -> Result<(), E> {
+        self.max6955.write_hex_u32(value, prefix)
+    }
+
+    /// Write Binary Number, see `Max6955::write_bin_u8`
+    pub fn write_bin_u8(&mut self, value: u8, prefix: bool) -> Result<(), E> {
+        self.max6955.write_bin_u8(value, prefix)
+    }
+
+    /// Write Hex-Decoded Digits, see `Max6955::write_decoded_digits`
+    pub fn write_decoded_digits(&mut self, codes: &[u8]) -> Result<(), E> {
+        self.max6955.write_decoded_digits(codes)
+    }
+
+    /// Write Unsigned Integer in Hex-Decode Mode, see `Max6955::write_decoded_u32`
+    pub fn write_decoded_u32(&mut self, value: u32) -> Result<(), E> {
+        self.max6955.write_decoded_u32(value)
+    }
+
+    /// Write Raw Segment Data, see `Max6955::write_segments`
+    pub fn write_segments(&mut self, segments: &[u8]) -> Result<(), E> {
+        self.max6955.write_segments(segments)
+    }
+
+    /// Shift the displayed text one position to the left, see `Max6955::shift_left`
+    pub fn shift_left(&mut self, fill: Option<char>) -> Result<(), E> {
+        self.max6955.shift_left(fill)
+    }
+
+    /// Append a Character, see `Max6955::push_char`
+    pub fn push_char(&mut self, c: char) -> Result<(), E> {
+        self.max6955.push_char(c)
+    }
+
+    /// Shift the displayed text one position to the right, see `Max6955::shift_right`
+    pub fn shift_right(&mut self, fill: Option<char>) -> Result<(), E> {
+        self.max6955.shift_right(fill)
+    }
+
+    /// Text last written to plane P0, see `Max6955::current_text`
+    pub fn current_text(&self) -> &str {
+        self.max6955.current_text()
+    }
+
+    /// Show a Message Briefly, see `Max6955::flash_message`
+    pub fn flash_message<D: DelayMs<u32>>(
+        &mut self,
+        text: &str,
+        duration_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), E> {
+        self.max6955.flash_message(text, duration_ms, delay)
+    }
+
+    /// Read Display, see `Max6955::read_display`
+    pub fn read_display(&mut self, plane: Plane) -> Result<[u8; 8], E> {
+        self.max6955.read_display(plane)
+    }
+
+    /// Blink a Single Digit, see `Max6955::set_digit_blink`
+    pub fn set_digit_blink(&mut self, digit: u8, enable: bool) -> Result<(), E> {
+        self.max6955.set_digit_blink(digit, enable)
+    }
+
+    /// Blink Selected Digits, see `Max6955::set_blink_mask`
+    pub fn set_blink_mask(&mut self, mask: u8, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_blink_mask(mask, rate)
+    }
+
+    /// Blinking Edit Cursor, see `Max6955::set_edit_cursor`
+    pub fn set_edit_cursor(&mut self, position: Option<u8>, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_edit_cursor(position, rate)
+    }
+
+    /// Set the Blink Timing Synchronization bit, see `Max6955::set_blink_timing`
+    pub fn set_blink_timing(&mut self, sync: BlinkSync) -> Result<(), E> {
+        self.max6955.set_blink_timing(sync)
+    }
+
+    /// Synchronize Blink Timing, see `Max6955::sync_blink_timing`
+    pub fn sync_blink_timing(&mut self) -> Result<(), E> {
+        self.max6955.sync_blink_timing()
+    }
+
+    /// Read Blink Phase, see `Max6955::blink_phase`
+    pub fn blink_phase(&mut self) -> Result<BlinkPhase, E> {
+        self.max6955.blink_phase()
+    }
+
+    /// Read Blink Configuration, see `Max6955::blink_config`
+    pub fn blink_config(&mut self) -> Result<BlinkConfig, E> {
+        self.max6955.blink_config()
+    }
+
+    /// Wait for the Blink Phase to Change, see `Max6955::wait_blink_edge`
+    pub fn wait_blink_edge<D: DelayMs<u32>>(
+        &mut self,
+        poll_ms: u32,
+        timeout_ms: u32,
+        delay: &mut D,
+    ) -> Result<Option<BlinkPhase>, E> {
+        self.max6955.wait_blink_edge(poll_ms, timeout_ms, delay)
+    }
+
+    /// Alternate Two Messages, see `Max6955::set_blink_messages`
+    pub fn set_blink_messages(&mut self, a: &str, b: &str, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_blink_messages(a, b, rate)
+    }
+
+    /// Swap Planes, see `Max6955::swap_planes`
+    pub fn swap_planes(&mut self) -> Result<(), E> {
+        self.max6955.swap_planes()
+    }
+
+    /// Select Global or Per-Digit Intensity, see `Max6955::set_intensity_mode`
+    pub fn set_intensity_mode(&mut self, mode: IntensityMode) -> Result<(), E> {
+        self.max6955.set_intensity_mode(mode)
+    }
+
+    /// Set Digit Intensity, see `Max6955::set_digit_intensity`
+    pub fn set_digit_intensity(&mut self, digit: u8, level: u8) -> Result<(), E> {
+        self.max6955.set_digit_intensity(digit, level)
+    }
+
+    /// Set All Digit Intensities in one transaction, see `Max6955::set_digit_intensities`
+    pub fn set_digit_intensities(&mut self, levels: &[u8; 8]) -> Result<(), E> {
+        self.max6955.set_digit_intensities(levels)
+    }
+
+    /// Set Digit Intensity in a bank, see `Max6955::set_bank_digit_intensity`
+    pub fn set_bank_digit_intensity(
+        &mut self,
+        bank: IntensityBank,
+        digit: u8,
+        level: u8,
+    ) -> Result<(), E> {
+        self.max6955.set_bank_digit_intensity(bank, digit, level)
+    }
+
+    /// Set All Digit Intensities of a bank, see `Max6955::set_bank_digit_intensities`
+    pub fn set_bank_digit_intensities(
+        &mut self,
+        bank: IntensityBank,
+        levels: &[u8; 8],
+    ) -> Result<(), E> {
+        self.max6955.set_bank_digit_intensities(bank, levels)
+    }
+
+    /// Read Global Intensity, see `Max6955::global_intensity`
+    pub fn global_intensity(&mut self) -> Result<u8, E> {
+        self.max6955.global_intensity()
+    }
+
+    /// Read Digit Intensity, see `Max6955::digit_intensity`
+    pub fn digit_intensity(&mut self, digit: u8) -> Result<u8, E> {
+        self.max6955.digit_intensity(digit)
+    }
+
+    /// Highlight Digit, see `Max6955::highlight_digit`
+    pub fn highlight_digit(&mut self, digit: u8, on: bool) -> Result<(), E> {
+        self.max6955.highlight_digit(digit, on)
+    }
+
+    /// Set Global Intensity in percent, see `Max6955::set_intensity_percent`
+    pub fn set_intensity_percent(&mut self, percent: u8) -> Result<(), E> {
+        self.max6955.set_intensity_percent(percent)
+    }
+
+    /// Set Intensity Calibration, see `Max6955::set_calibration`
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.max6955.set_calibration(calibration)
+    }
+
+    /// Set the brightness curve of the percent and fade APIs, see `Max6955::set_brightness_curve`
+    pub fn set_brightness_curve(&mut self, curve: BrightnessCurve) {
+        self.max6955.set_brightness_curve(curve)
+    }
+
+    /// Fade Global Intensity, see `Max6955::fade_to`
+    pub fn fade_to<D: DelayMs<u32>>(
+        &mut self,
+        target: u8,
+        step_ms: u32,
+        delay: &mut D,
+    ) -> Result<(), E> {
+        self.max6955.fade_to(target, step_ms, delay)
+    }
+
+    /// Reload the driver's copy of the configuration register, see `Max6955::resync`
+    pub fn resync(&mut self) -> Result<(), E> {
+        self.max6955.config = None;
+        self.max6955.config()?;
+        Ok(())
+    }
+
+    /// Release the I2C interface
+    pub fn release(self) -> I2C {
+        self.max6955.i2c
+    }
+}
+
+/// Key scan part of a MAX6955, see `Max6955::split`
+pub struct KeyScan<I2C> {
+    max6955: Max6955<I2C>,
+}
+
+impl<I2C, E> KeyScan<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Read Pressed Keys, see `Max6955::read_keys`
+    pub fn read_keys(&mut self) -> Result<u32, E> {
+        self.max6955.read_keys()
+    }
+
+    /// Read Debounced Keys, see `Max6955::read_debounced_keys`
+    pub fn read_debounced_keys(&mut self) -> Result<u32, E> {
+        self.max6955.read_debounced_keys()
+    }
+
+    /// Read the Keys behind a Key Interrupt, see `Max6955::read_key_interrupt`
+    pub fn read_key_interrupt(&mut self) -> Result<KeyInterrupt, E> {
+        self.max6955.read_key_interrupt()
+    }
+
+    /// Wait for a Key Press, see `Max6955::wait_for_key`
+    pub fn wait_for_key<P: InputPin<Error = PE>, PE, D: DelayMs<u32>>(
+        &mut self,
+        irq: &P,
+        poll_ms: u32,
+        timeout_ms: u32,
+        delay: &mut D,
+    ) -> Result<Option<u32>, KeyWaitError<E, PE>> {
+        self.max6955.wait_for_key(irq, poll_ms, timeout_ms, delay)
+    }
+
+    /// Set Key Mask, see `Max6955::set_key_mask`
+    pub fn set_key_mask(&mut self, bank: KeyBank, mask: u8) -> Result<(), E> {
+        self.max6955.set_key_mask(bank, mask)
+    }
+
+    /// Set All Key Masks, see `Max6955::set_key_masks`
+    pub fn set_key_masks(&mut self, masks: u32) -> Result<(), E> {
+        self.max6955.set_key_masks(masks)
+    }
+
+    /// Key Mask of a bank as last set, see `Max6955::key_mask`
+    pub fn key_mask(&self, bank: KeyBank) -> u8 {
+        self.max6955.key_mask(bank)
+    }
+
+    /// All Key Masks as last set, see `Max6955::key_masks`
+    pub fn key_masks(&self) -> u32 {
+        self.max6955.key_masks()
+    }
+
+    /// Rewrite the Key Masks as last set, see `Max6955::restore_key_masks`
+    pub fn restore_key_masks(&mut self) -> Result<(), E> {
+        self.max6955.restore_key_masks()
+    }
+
+    /// Release the I2C interface
+    pub fn release(self) -> I2C {
+        self.max6955.i2c
+    }
+}
+
+/// GPIO part of a MAX6955, see `Max6955::split`
+///
+/// Keeps its own copies of the port configuration and the output levels, so pin changes are
+/// single writes.
+pub struct Gpio<I2C> {
+    max6955: Max6955<I2C>,
+}
+
+impl<I2C, E> Gpio<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Configure Pin Mode, see `Max6955::set_pin_mode`
+    pub fn set_pin_mode(&mut self, port: usize, pin_mode: PinMode) -> Result<(), E> {
+        self.max6955.set_pin_mode(port, pin_mode)
+    }
+
+    /// Read GPIO, see `Max6955::read_gpio`
+    pub fn read_gpio(&mut self) -> Result<u8, E> {
+        self.max6955.read_gpio()
+    }
+
+    /// Read Port Pin, see `Max6955::read_pin`
+    pub fn read_pin(&mut self, port: usize) -> Result<bool, E> {
+        self.max6955.read_pin(port)
+    }
+
+    /// Write GPIO, see `Max6955::write_gpio`
+    pub fn write_gpio(&mut self, value: u8) -> Result<(), E> {
+        self.max6955.write_gpio(value)
+    }
+
+    /// Set Port Pin, see `Max6955::set_pin`
+    pub fn set_pin(&mut self, port: usize, level: bool) -> Result<(), E> {
+        self.max6955.set_pin(port, level)
+    }
+
+    /// Change several Port Pins, see `Max6955::modify_gpio`
+    pub fn modify_gpio(&mut self, set_mask: u8, clear_mask: u8) -> Result<(), E> {
+        self.max6955.modify_gpio(set_mask, clear_mask)
+    }
+
+    /// Toggle Port Pin, see `Max6955::toggle_pin`
+    pub fn toggle_pin(&mut self, port: usize) -> Result<(), E> {
+        self.max6955.toggle_pin(port)
+    }
+
+    /// Configure all Port Pins, see `Max6955::set_port_config`
+    pub fn set_port_config(&mut self, config: PortConfig) -> Result<(), E> {
+        self.max6955.set_port_config(config)
+    }
+
+    /// Read Port Configuration, see `Max6955::port_config`
+    pub fn port_config(&mut self) -> Result<PortConfig, E> {
+        self.max6955.port_config()
+    }
+
+    /// Reload the port configuration and output levels, see `Max6955::resync_gpio`
+    pub fn resync(&mut self) -> Result<(), E> {
+        self.max6955.resync_gpio()
+    }
+
+    /// Enable the Key Interrupt Output, see `Max6955::enable_key_interrupt`
+    pub fn enable_key_interrupt(&mut self, enable: bool) -> Result<(), E> {
+        self.max6955.enable_key_interrupt(enable)
+    }
+
+    /// Release the I2C interface
+    pub fn release(self) -> I2C {
+        self.max6955.i2c
+    }
+}
@@ -0,0 +1,162 @@
+//! Snapshot of the device registers
+
+use super::{Interface, Max6955, Plane, Register, CONFIG_TRANSIENT, MAX_INTENSITY};
+
+/// Register values of a MAX6955, e.g. for logging
+///
+/// The digit type register and the key mask registers cannot be read back. Their values are
+/// the ones last written through this driver.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeviceState {
+    /// Decode mode register
+    pub decode_mode: u8,
+    /// Global intensity register
+    pub global_intensity: u8,
+    /// Scan limit register
+    pub scan_limit: u8,
+    /// Configuration register
+    pub configuration: u8,
+    /// GPIO data register
+    pub gpio: u8,
+    /// Port configuration register
+    pub port_configuration: u8,
+    /// Display test register
+    pub display_test: u8,
+    /// Digit type register as last written
+    pub digit_type: u8,
+    /// Key mask registers as last written, key A0 in bit 0 ~ key D7 in bit 31
+    pub key_masks: u32,
+    /// Intensity registers Intensity10 ~ Intensity76a
+    pub intensities: [u8; 8],
+    /// Digit registers of planes P0 and P1 in register order
+    pub planes: [[u8; 8]; 2],
+}
+
+impl DeviceState {
+    /// Register values after power-on, digits blank, see Table 6
+    pub const POWER_ON: DeviceState = DeviceState {
+        decode_mode: 0x00,
+        global_intensity: 0x00,
+        scan_limit: 0x07,
+        configuration: 0x00,
+        gpio: 0x00,
+        port_configuration: 0x00,
+        display_test: 0x00,
+        digit_type: 0x00,
+        key_masks: 0,
+        intensities: [0x00; 8],
+        planes: [[b' '; 8]; 2],
+    };
+}
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Read Device State
+    ///
+    /// Reads the control, intensity and digit registers in four transactions.
+    /// The key registers are not read, so no key press is lost.
+    pub fn dump_state(&mut self) -> Result<DeviceState, E> {
+        let mut control: [u8; 7] = [0; 7];
+        self.read_registers(Register::DecodeMode, &mut control)?;
+        let mut intensities: [u8; 8] = [0; 8];
+        self.read_registers(Register::Intensity10, &mut intensities)?;
+        let mut planes: [[u8; 8]; 2] = [[0; 8]; 2];
+        self.read_registers(Plane::P0.digit0(), &mut planes[0])?;
+        self.read_registers(Plane::P1.digit0(), &mut planes[1])?;
+        Ok(DeviceState {
+            decode_mode: control[0],
+            global_intensity: control[1],
+            scan_limit: control[2],
+            configuration: control[3],
+            gpio: control[4],
+            port_configuration: control[5],
+            display_test: control[6],
+            digit_type: self.digit_type,
+            key_masks: self.key_masks,
+            intensities,
+            planes,
+        })
+    }
+
+    /// Restore Device State
+    ///
+    /// Writes all writable registers from a snapshot in six transactions, and takes the
+    /// driver's copies of them from the snapshot. The clear and blink timing reset bits of the
+    /// configuration are not applied, per-digit blinking is turned off and the cursor returns
+    /// to the first position.
+    /// # Arguments
+    ///
+    /// * `state` - snapshot from `dump_state`
+    pub fn restore_state(&mut self, state: &DeviceState) -> Result<(), E> {
+        let configuration = state.configuration & !CONFIG_TRANSIENT;
+        self.write_registers(
+            Register::DecodeMode,
+            &[
+                state.decode_mode,
+                state.global_intensity,
+                state.scan_limit,
+                configuration,
+                state.gpio,
+                state.port_configuration,
+                state.display_test,
+            ],
+        )?;
+        self.write_register(Register::DigitType, state.digit_type)?;
+        self.set_key_masks(state.key_masks)?;
+        self.write_registers(Register::Intensity10, &state.intensities)?;
+        self.write_registers(Plane::P0.digit0(), &state.planes[0])?;
+        self.write_registers(Plane::P1.digit0(), &state.planes[1])?;
+        self.store_config(configuration);
+        self.intensity = self
+            .calibration
+            .level(state.global_intensity & MAX_INTENSITY);
+        self.port_config = Some(state.port_configuration);
+        self.outputs = Some(state.gpio);
+        self.digit_type = state.digit_type;
+        self.digits = (state.scan_limit & 0x07) as usize + 1;
+        self.blink_mask = 0;
+        self.cursor = 0;
+        let map = self.digit_map.limit(self.digits);
+        for position in 0..8 {
+            self.store(position, state.planes[0][map.digit(position)]);
+        }
+        Ok(())
+    }
+
+    /// Reset to Defaults
+    ///
+    /// Writes the power-on values to all writable registers, which brings a device that kept
+    /// running through a restart of the microcontroller back to a known state. The device
+    /// ends up shut down.
+    pub fn reset_to_defaults(&mut self) -> Result<(), E> {
+        self.restore_state(&DeviceState::POWER_ON)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeviceState;
+    use mock::Mock;
+    use {Calibration, Max6955};
+
+    #[test]
+    fn restore_takes_the_global_level_from_the_snapshot() {
+        let mut max6955 = Max6955::new(Mock::new()).unwrap();
+        max6955.set_calibration(Calibration {
+            scale: 100,
+            offset: 2,
+        });
+        max6955.set_global_intensity(3).unwrap();
+        max6955.write_str("AB").unwrap();
+        let state = DeviceState {
+            global_intensity: 9,
+            ..DeviceState::POWER_ON
+        };
+        max6955.restore_state(&state).unwrap();
+        assert_eq!(max6955.intensity, 7);
+        assert_eq!(max6955.cursor(), 0);
+        assert_eq!(max6955.global_intensity().unwrap(), 7);
+    }
+}
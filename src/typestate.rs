@@ -0,0 +1,261 @@
+//! Power state checked at compile time
+
+use super::{
+    BlinkMode, BlinkRate, DateFormat, DecodeModeConfig, DigitTypeConfig, Interface, Max6955,
+    Overflow, Padding, ScanLimit, Separator, TimeFormat, Unit,
+};
+use core::marker::PhantomData;
+#[cfg(feature = "fixed")]
+use fixed::{types::extra::LeEqU32, FixedI32};
+
+/// Power state of a shut down display
+pub struct Shutdown;
+
+/// Power state of a powered display
+pub struct Running;
+
+/// MAX6955 driver that tracks the power state in its type
+///
+/// Setup is done in the `Shutdown` state and text is only written in the `Running` state,
+/// so writing to a display that was never powered up fails to build.
+pub struct TypedMax6955<I2C, STATE> {
+    max6955: Max6955<I2C>,
+    state: PhantomData<STATE>,
+}
+
+impl<I2C, E, STATE> TypedMax6955<I2C, STATE>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Set Global Intensity, see `Max6955::set_global_intensity`
+    pub fn set_global_intensity(&mut self, intensity: u8) -> Result<(), E> {
+        self.max6955.set_global_intensity(intensity)
+    }
+
+    /// Control Blinking, see `Max6955::set_blink`
+    pub fn set_blink(&mut self, mode: BlinkMode, rate: BlinkRate) -> Result<(), E> {
+        self.max6955.set_blink(mode, rate)
+    }
+
+    /// Set Overflow Behavior, see `Max6955::set_overflow`
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.max6955.set_overflow(overflow)
+    }
+
+    /// Set how the numeric writes pad numbers, see `Max6955::set_padding`
+    pub fn set_padding(&mut self, padding: Padding) {
+        self.max6955.set_padding(padding)
+    }
+
+    /// Set the thousands separator of decimal numeric writes, see `Max6955::set_separator`
+    pub fn set_separator(&mut self, separator: Separator) {
+        self.max6955.set_separator(separator)
+    }
+
+    /// Release the MAX6955 driver
+    pub fn release(self) -> Max6955<I2C> {
+        self.max6955
+    }
+}
+
+impl<I2C, E> TypedMax6955<I2C, Shutdown>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Shut down the display and track its power state
+    ///
+    /// # Arguments
+    ///
+    /// * `max6955` - MAX6955 driver
+    ///
+    /// # Errors
+    ///
+    /// * `E` - returned in case there was an error writing the configuration
+    ///
+    pub fn new(mut max6955: Max6955<I2C>) -> Result<Self, E> {
+        max6955.shutdown()?;
+        Ok(TypedMax6955 {
+            max6955,
+            state: PhantomData,
+        })
+    }
+
+    /// Set Scan Limit, see `Max6955::set_scan_limit`
+    pub fn set_scan_limit(&mut self, limit: ScanLimit) -> Result<(), E> {
+        self.max6955.set_scan_limit(limit)
+    }
+
+    /// Configure Decode Mode, see `Max6955::set_decode_mode`
+    pub fn set_decode_mode<T: Into<DecodeModeConfig>>(&mut self, mode: T) -> Result<(), E> {
+        self.max6955.set_decode_mode(mode)
+    }
+
+    /// Configure Digit Type, see `Max6955::set_digit_type`
+    pub fn set_digit_type<T: Into<DigitTypeConfig>>(&mut self, digit_type: T) -> Result<(), E> {
+        self.max6955.set_digit_type(digit_type)
+    }
+
+    /// Power up Display
+    pub fn powerup(mut self) -> Result<TypedMax6955<I2C, Running>, E> {
+        self.max6955.powerup()?;
+        Ok(TypedMax6955 {
+            max6955: self.max6955,
+            state: PhantomData,
+        })
+    }
+}
+
+impl<I2C, E> TypedMax6955<I2C, Running>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Write a character at the cursor and advance the cursor, see `Max6955::write_char`
+    pub fn write_char(&mut self, c: char) -> Result<(), E> {
+        self.max6955.write_char(c)
+    }
+
+    /// Write Text, see `Max6955::write_str`
+    pub fn write_str(&mut self, text: &str) -> Result<(), E> {
+        self.max6955.write_str(text)
+    }
+
+    /// Write Characters, see `Max6955::write_iter`
+    pub fn write_iter(&mut self, chars: impl Iterator<Item = char>) -> Result<(), E> {
+        self.max6955.write_iter(chars)
+    }
+
+    /// Write ASCII Text, see `Max6955::write_ascii`
+    pub fn write_ascii(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.max6955.write_ascii(bytes)
+    }
+
+    /// Write Unsigned Integer, see `Max6955::write_u32`
+    pub fn write_u32(&mut self, value: u32) -> Result<(), E> {
+        self.max6955.write_u32(value)
+    }
+
+    /// Write Signed Integer, see `Max6955::write_i32`
+    pub fn write_i32(&mut self, value: i32) -> Result<(), E> {
+        self.max6955.write_i32(value)
+    }
+
+    /// Write Floating Point Number, see `Max6955::write_f32`
+    pub fn write_f32(&mut self, value: f32, decimals: usize) -> Result<(), E> {
+        self.max6955.write_f32(value, decimals)
+    }
+
+    /// Write Fixed-Point Number, see `Max6955::write_fixed`
+    #[cfg(feature = "fixed")]
+    pub fn write_fixed<Frac: LeEqU32>(
+        &mut self,
+        value: FixedI32<Frac>,
+        decimals: usize,
+    ) -> Result<(), E> {
+        self.max6955.write_fixed(value, decimals)
+    }
+
+    /// Write Temperature, see `Max6955::write_temperature`
+    pub fn write_temperature(&mut self, value_c_x10: i16, unit: Unit) -> Result<(), E> {
+        self.max6955.write_temperature(value_c_x10, unit)
+    }
+
+    /// Write Time, see `Max6955::write_time`
+    pub fn write_time(
+        &mut self,
+        hours: u8,
+        minutes: u8,
+        seconds: u8,
+        format: TimeFormat,
+        blink_colon: bool,
+    ) -> Result<(), E> {
+        self.max6955
+            .write_time(hours, minutes, seconds, format, blink_colon)
+    }
+
+    /// Write Date, see `Max6955::write_date`
+    pub fn write_date(
+        &mut self,
+        day: u8,
+        month: u8,
+        year: u16,
+        format: DateFormat,
+    ) -> Result<(), E> {
+        self.max6955.write_date(day, month, year, format)
+    }
+
+    /// Write Value with SI Prefix, see `Max6955::write_engineering`
+    pub fn write_engineering(&mut self, value: f32, unit: &str) -> Result<(), E> {
+        self.max6955.write_engineering(value, unit)
+    }
+
+    /// Write Hexadecimal Number, see `Max6955::write_hex_u32`
+    pub fn write_hex_u32(&mut self, value: u32, prefix: bool) -> Result<(), E> {
+        self.max6955.write_hex_u32(value, prefix)
+    }
+
+    /// Write Binary Number, see `Max6955::write_bin_u8`
+    pub fn write_bin_u8(&mut self, value: u8, prefix: bool) -> Result<(), E> {
+        self.max6955.write_bin_u8(value, prefix)
+    }
+
+    /// Write Hex-Decoded Digits, see `Max6955::write_decoded_digits`
+    pub fn write_decoded_digits(&mut self, codes: &[u8]) -> Result<(), E> {
+        self.max6955.write_decoded_digits(codes)
+    }
+
+    /// Write Unsigned Integer in Hex-Decode Mode, see `Max6955::write_decoded_u32`
+    pub fn write_decoded_u32(&mut self, value: u32) -> Result<(), E> {
+        self.max6955.write_decoded_u32(value)
+    }
+
+    /// Write Raw Segment Data, see `Max6955::write_segments`
+    pub fn write_segments(&mut self, segments: &[u8]) -> Result<(), E> {
+        self.max6955.write_segments(segments)
+    }
+
+    /// Shift the displayed text one position to the left, see `Max6955::shift_left`
+    pub fn shift_left(&mut self, fill: Option<char>) -> Result<(), E> {
+        self.max6955.shift_left(fill)
+    }
+
+    /// Append a Character, see `Max6955::push_char`
+    pub fn push_char(&mut self, c: char) -> Result<(), E> {
+        self.max6955.push_char(c)
+    }
+
+    /// Shift the displayed text one position to the right, see `Max6955::shift_right`
+    pub fn shift_right(&mut self, fill: Option<char>) -> Result<(), E> {
+        self.max6955.shift_right(fill)
+    }
+
+    /// Text last written to plane P0, see `Max6955::current_text`
+    pub fn current_text(&self) -> &str {
+        self.max6955.current_text()
+    }
+
+    /// Shutdown Display
+    pub fn shutdown(mut self) -> Result<TypedMax6955<I2C, Shutdown>, E> {
+        self.max6955.shutdown()?;
+        Ok(TypedMax6955 {
+            max6955: self.max6955,
+            state: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypedMax6955;
+    use mock::Mock;
+    use {Max6955, Register};
+
+    #[test]
+    fn running_display_writes_numbers_while_powered() {
+        let typed = TypedMax6955::new(Max6955::new(Mock::new()).unwrap()).unwrap();
+        let mut typed = typed.powerup().unwrap();
+        typed.write_u32(42).unwrap();
+        let max6955 = typed.release();
+        assert_eq!(max6955.i2c.get(Register::Configuration) & 0x01, 0x01);
+        assert_eq!(max6955.current_text().trim(), "42");
+    }
+}
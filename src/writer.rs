@@ -0,0 +1,56 @@
+//! [`ufmt::uWrite`] support
+//!
+//! Enabled with the `ufmt` feature.
+
+use super::{ascii, Interface, Max6955};
+use ufmt::uWrite;
+
+/// Text writer for the `uwrite!`/`uwriteln!` macros.
+///
+/// Created by [`Max6955::writer`]. Fragments are appended from the leftmost digit and
+/// the display is updated after each fragment. Text beyond the scan limit is handled
+/// as set by [`Max6955::set_overflow`].
+pub struct Writer<'a, I2C: 'a> {
+    max6955: &'a mut Max6955<I2C>,
+    digits: [u8; 8],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<I2C, E> Max6955<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Start a new line of text for `uwrite!`. The display is blanked on the first write.
+    pub fn writer(&mut self) -> Writer<'_, I2C> {
+        Writer {
+            max6955: self,
+            digits: [b' '; 8],
+            len: 0,
+            overflowed: false,
+        }
+    }
+}
+
+impl<'a, I2C, E> uWrite for Writer<'a, I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    type Error = E;
+
+    fn write_str(&mut self, s: &str) -> Result<(), E> {
+        for c in s.chars() {
+            if self.len == self.max6955.digits {
+                self.overflowed = true;
+                break;
+            }
+            self.digits[self.len] = ascii(c);
+            self.len += 1;
+        }
+        let mut digits = self.digits;
+        if self.overflowed {
+            self.max6955.overflow.apply(&mut digits);
+        }
+        self.max6955.write_digits(&digits)
+    }
+}